@@ -0,0 +1,406 @@
+use crate::element::{
+    AnyType, CallableAttrs, Field, Method, Namespace, Parameters, Property, ReturnValue,
+};
+use crate::generator::Gir;
+use crate::library::Library;
+use std::fmt;
+
+#[derive(Debug, Clone)]
+pub enum ProblemKind {
+    /// Type name that does not resolve to any loaded type.
+    DanglingType(String),
+    /// `closure`, `destroy` or array `length` pointing past the parameter list.
+    IndexOutOfRange {
+        attribute: &'static str,
+        index: i32,
+        parameters: usize,
+    },
+    /// `<include>` with no matching repository in the loaded dirs.
+    MissingInclude { name: String, version: String },
+    /// `shadows` or `shadowed-by` without the matching attribute on the other callable.
+    UnmatchedShadow {
+        attribute: &'static str,
+        target: String,
+    },
+    /// Property `getter` or `setter` naming a method that does not exist.
+    MissingAccessor {
+        attribute: &'static str,
+        method: String,
+    },
+}
+
+impl fmt::Display for ProblemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProblemKind::DanglingType(name) => write!(f, "unknown type {name}"),
+            ProblemKind::IndexOutOfRange {
+                attribute,
+                index,
+                parameters,
+            } => write!(
+                f,
+                "{attribute} index {index} out of range for {parameters} parameters"
+            ),
+            ProblemKind::MissingInclude { name, version } => {
+                write!(f, "included {name}-{version} is not loaded")
+            }
+            ProblemKind::UnmatchedShadow { attribute, target } => {
+                write!(f, "{attribute} {target} has no matching callable")
+            }
+            ProblemKind::MissingAccessor { attribute, method } => {
+                write!(f, "{attribute} {method} is not a method")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Problem {
+    /// Repository the problem was found in, e.g "Gtk-4.0"
+    pub repo: String,
+    /// Dotted path to the offending element, e.g "Gtk.Widget.show"
+    pub path: String,
+    pub kind: ProblemKind,
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}: {}", self.repo, self.path, self.kind)
+    }
+}
+
+macro_rules! check_callables {
+    ($checker:expr, $path:expr, $callables:expr) => {
+        for c in &$callables {
+            let path = format!("{}.{}", $path, c.attrs.name);
+            $checker.callable(&path, c.parameters.as_ref(), c.return_value.as_ref());
+        }
+    };
+}
+
+/// Type names that refer to other elements, lowercase names are fundamental types.
+fn is_reference(name: &str) -> bool {
+    name.contains('.') || (name.starts_with(|c: char| c.is_ascii_uppercase()) && name != "GType")
+}
+
+struct Checker<'l, 'a> {
+    library: &'l Library<'a>,
+    namespace: &'a Namespace,
+    repo: &'a str,
+    problems: Vec<Problem>,
+}
+
+impl Checker<'_, '_> {
+    fn report(&mut self, path: &str, kind: ProblemKind) {
+        self.problems.push(Problem {
+            repo: self.repo.to_owned(),
+            path: path.to_owned(),
+            kind,
+        });
+    }
+
+    fn r#type(&mut self, path: &str, r#type: Option<&AnyType>) {
+        let (name, elements) = match r#type {
+            Some(AnyType::Type(t)) => (t.name.as_deref(), &t.elements),
+            Some(AnyType::Array(a)) => (a.name.as_deref(), &a.elements),
+            None => return,
+        };
+
+        if let Some(name) = name.filter(|name| is_reference(name)) {
+            // unloaded namespaces are reported once as a missing include instead
+            let loaded = name
+                .split_once('.')
+                .is_none_or(|(ns, _)| !self.library.versions(ns).is_empty());
+            let resolved = self
+                .library
+                .resolve(self.namespace, name)
+                .is_some_and(|entry| entry.symbol.is_type());
+
+            if loaded && !resolved {
+                self.report(path, ProblemKind::DanglingType(name.to_owned()));
+            }
+        }
+
+        for element in elements {
+            self.r#type(path, Some(element));
+        }
+    }
+
+    fn index(&mut self, path: &str, attribute: &'static str, index: Option<i32>, len: usize) {
+        if let Some(index) = index
+            && usize::try_from(index).is_ok_and(|i| i >= len)
+        {
+            let kind = ProblemKind::IndexOutOfRange {
+                attribute,
+                index,
+                parameters: len,
+            };
+            self.report(path, kind);
+        }
+    }
+
+    fn array_length(&mut self, path: &str, r#type: Option<&AnyType>, len: usize) {
+        if let Some(AnyType::Array(array)) = r#type {
+            self.index(path, "length", array.length, len);
+        }
+    }
+
+    fn callable(&mut self, path: &str, parameters: Option<&Parameters>, ret: Option<&ReturnValue>) {
+        let params = parameters
+            .map(|p| p.parameters.as_slice())
+            .unwrap_or_default();
+        let len = params.len();
+
+        if let Some(ret) = ret {
+            self.r#type(path, ret.r#type.as_ref());
+            self.array_length(path, ret.r#type.as_ref(), len);
+        }
+
+        if let Some(instance) = parameters.and_then(|p| p.instance_parameter.as_ref()) {
+            self.r#type(path, instance.r#type.as_ref());
+        }
+
+        for (i, param) in params.iter().enumerate() {
+            let path = format!(
+                "{path}({})",
+                param.name.as_deref().unwrap_or(&i.to_string())
+            );
+            self.r#type(&path, param.r#type.as_ref());
+            self.array_length(&path, param.r#type.as_ref(), len);
+            self.index(&path, "closure", param.closure, len);
+            self.index(&path, "destroy", param.destroy, len);
+        }
+    }
+
+    fn shadows(&mut self, path: &str, callables: &[&CallableAttrs]) {
+        let find = |name: &str| callables.iter().find(|c| c.name == name);
+
+        for callable in callables {
+            let path = format!("{path}.{}", callable.name);
+
+            if let Some(target) = &callable.shadows
+                && find(target).is_none_or(|t| t.shadowed_by.as_ref() != Some(&callable.name))
+            {
+                let kind = ProblemKind::UnmatchedShadow {
+                    attribute: "shadows",
+                    target: target.clone(),
+                };
+                self.report(&path, kind);
+            }
+
+            if let Some(target) = &callable.shadowed_by
+                && find(target).is_none_or(|t| t.shadows.as_ref() != Some(&callable.name))
+            {
+                let kind = ProblemKind::UnmatchedShadow {
+                    attribute: "shadowed-by",
+                    target: target.clone(),
+                };
+                self.report(&path, kind);
+            }
+        }
+    }
+
+    fn properties(&mut self, path: &str, properties: &[Property], methods: &[Method]) {
+        for property in properties {
+            let path = format!("{path}:{}", property.name);
+            self.r#type(&path, property.r#type.as_ref());
+
+            let accessors = [("getter", &property.getter), ("setter", &property.setter)];
+            for (attribute, method) in accessors {
+                if let Some(method) = method
+                    && !methods.iter().any(|m| m.attrs.name == *method)
+                {
+                    let kind = ProblemKind::MissingAccessor {
+                        attribute,
+                        method: method.clone(),
+                    };
+                    self.report(&path, kind);
+                }
+            }
+        }
+    }
+
+    fn fields(&mut self, path: &str, fields: &[Field]) {
+        for field in fields {
+            let path = format!("{path}.{}", field.name);
+            self.r#type(&path, field.r#type.as_ref());
+            if let Some(callback) = &field.callback {
+                self.callable(
+                    &path,
+                    callback.parameter.as_ref(),
+                    callback.return_value.as_ref(),
+                );
+            }
+        }
+    }
+
+    fn namespace(&mut self) {
+        let ns = self.namespace;
+        let name = &ns.name;
+
+        for class in &ns.classes {
+            let path = format!("{name}.{}", class.name);
+            for parent in class.parent.iter() {
+                self.r#type(&path, Some(&named(parent)));
+            }
+            for iface in &class.implements {
+                self.r#type(&path, Some(&named(&iface.name)));
+            }
+            check_callables!(self, path, class.constructors);
+            check_callables!(self, path, class.methods);
+            check_callables!(self, path, class.functions);
+            check_callables!(self, path, class.virtual_methods);
+            for s in &class.signals {
+                let path = format!("{path}::{}", s.name);
+                self.callable(&path, s.parameters.as_ref(), s.return_value.as_ref());
+            }
+            self.properties(&path, &class.properties, &class.methods);
+            self.fields(&path, &class.fields);
+
+            let callables: Vec<&CallableAttrs> = class
+                .constructors
+                .iter()
+                .map(|c| &c.attrs)
+                .chain(class.methods.iter().map(|m| &m.attrs))
+                .chain(class.functions.iter().map(|f| &f.attrs))
+                .collect();
+            self.shadows(&path, &callables);
+        }
+
+        for iface in &ns.interfaces {
+            let path = format!("{name}.{}", iface.name);
+            for prerequisite in &iface.prerequisites {
+                self.r#type(&path, Some(&named(&prerequisite.name)));
+            }
+            check_callables!(self, path, iface.constructors);
+            check_callables!(self, path, iface.methods);
+            check_callables!(self, path, iface.functions);
+            check_callables!(self, path, iface.virtual_methods);
+            for s in &iface.signals {
+                let path = format!("{path}::{}", s.name);
+                self.callable(&path, s.parameters.as_ref(), s.return_value.as_ref());
+            }
+            self.properties(&path, &iface.properties, &iface.methods);
+
+            let callables: Vec<&CallableAttrs> = iface
+                .constructors
+                .iter()
+                .map(|c| &c.attrs)
+                .chain(iface.methods.iter().map(|m| &m.attrs))
+                .chain(iface.functions.iter().map(|f| &f.attrs))
+                .collect();
+            self.shadows(&path, &callables);
+        }
+
+        for record in &ns.records {
+            let path = format!("{name}.{}", record.name);
+            check_callables!(self, path, record.constructors);
+            check_callables!(self, path, record.methods);
+            check_callables!(self, path, record.functions);
+            self.fields(&path, &record.fields);
+
+            let callables: Vec<&CallableAttrs> = record
+                .constructors
+                .iter()
+                .map(|c| &c.attrs)
+                .chain(record.methods.iter().map(|m| &m.attrs))
+                .chain(record.functions.iter().map(|f| &f.attrs))
+                .collect();
+            self.shadows(&path, &callables);
+        }
+
+        for union in &ns.unions {
+            let path = format!("{name}.{}", union.name.as_deref().unwrap_or_default());
+            check_callables!(self, path, union.constructors);
+            check_callables!(self, path, union.methods);
+            check_callables!(self, path, union.functions);
+            self.fields(&path, &union.fields);
+        }
+
+        for (enum_name, functions) in ns
+            .enums
+            .iter()
+            .map(|e| (&e.name, &e.functions))
+            .chain(ns.bitfields.iter().map(|b| (&b.name, &b.functions)))
+        {
+            for f in functions {
+                let path = format!("{name}.{enum_name}.{}", f.attrs.name);
+                self.callable(&path, f.parameters.as_ref(), f.return_value.as_ref());
+            }
+        }
+
+        for callback in &ns.callbacks {
+            let path = format!("{name}.{}", callback.name);
+            self.callable(
+                &path,
+                callback.parameter.as_ref(),
+                callback.return_value.as_ref(),
+            );
+        }
+
+        for alias in &ns.aliases {
+            self.r#type(&format!("{name}.{}", alias.name), alias.r#type.as_ref());
+        }
+
+        for constant in &ns.constants {
+            self.r#type(
+                &format!("{name}.{}", constant.name),
+                constant.r#type.as_ref(),
+            );
+        }
+
+        for f in &ns.functions {
+            let path = format!("{name}.{}", f.attrs.name);
+            self.callable(&path, f.parameters.as_ref(), f.return_value.as_ref());
+        }
+        let callables: Vec<&CallableAttrs> = ns.functions.iter().map(|f| &f.attrs).collect();
+        self.shadows(name, &callables);
+    }
+}
+
+fn named(name: &str) -> AnyType {
+    AnyType::Type(crate::element::Type {
+        name: Some(name.to_owned()),
+        c_type: None,
+        introspectable: None,
+        doc_elements: Vec::new(),
+        elements: Vec::new(),
+    })
+}
+
+/// Report semantic problems the parser accepts, such as references to types that do not exist.
+pub fn validate(girs: &[Gir]) -> Vec<Problem> {
+    let library = Library::new(girs);
+    let mut problems = Vec::new();
+
+    for gir in girs {
+        for include in &gir.repo.includes {
+            if library
+                .repository(&include.name, &include.version)
+                .is_none()
+            {
+                problems.push(Problem {
+                    repo: gir.name.to_owned(),
+                    path: String::from("include"),
+                    kind: ProblemKind::MissingInclude {
+                        name: include.name.clone(),
+                        version: include.version.clone(),
+                    },
+                });
+            }
+        }
+
+        for namespace in &gir.repo.namespaces {
+            let mut checker = Checker {
+                library: &library,
+                namespace,
+                repo: gir.name,
+                problems: Vec::new(),
+            };
+            checker.namespace();
+            problems.extend(checker.problems);
+        }
+    }
+
+    problems
+}
@@ -0,0 +1,134 @@
+use crate::generator::cache;
+use crate::pattern::Pattern;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use std::{fmt, fs, io};
+
+pub const FILE_NAME: &str = "girgen.toml";
+
+/// Project configuration read from a `girgen.toml`.
+/// Relative paths are resolved against the directory of the file.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct Config {
+    /// Extra directories for .gir lookup
+    pub dirs: Vec<PathBuf>,
+    pub ignore: Vec<Pattern>,
+    pub include: Vec<Pattern>,
+    pub only: Vec<String>,
+    pub files: Vec<PathBuf>,
+    pub strict_parse: bool,
+    pub strict: bool,
+    pub max_failures: Option<usize>,
+    pub gjs: GjsConfig,
+    pub gir: GirConfig,
+    pub cache: CacheConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct GjsConfig {
+    pub outdir: Option<PathBuf>,
+    pub alias: bool,
+    /// Files named after a namespace, e.g "Gtk-4.0.d.ts", appended to its declarations
+    pub overrides: Vec<PathBuf>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct GirConfig {
+    pub outdir: Option<PathBuf>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct CacheConfig {
+    pub enabled: bool,
+    pub dir: Option<PathBuf>,
+    /// Total size in MiB after which the least recently used entries are evicted
+    pub max_size: Option<u64>,
+    /// Days after which unused entries are evicted
+    pub max_age: Option<u64>,
+    /// Evict entries written by other girgen versions
+    pub drop_other_versions: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            dir: None,
+            max_size: None,
+            max_age: None,
+            drop_other_versions: true,
+        }
+    }
+}
+
+impl CacheConfig {
+    /// Eviction policy, limits that are not set keep their defaults
+    pub fn policy(&self) -> cache::Policy {
+        let default = cache::Policy::default();
+        cache::Policy {
+            max_size: self
+                .max_size
+                .map(|mib| mib * 1024 * 1024)
+                .or(default.max_size),
+            max_age: self
+                .max_age
+                .map(|days| Duration::from_secs(days * 24 * 60 * 60))
+                .or(default.max_age),
+            drop_other_versions: self.drop_other_versions,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io(PathBuf, io::Error),
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(path, err) => write!(f, "could not read {}: {}", path.display(), err),
+            ConfigError::Parse(path, err) => write!(f, "invalid {}: {}", path.display(), err),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl Config {
+    /// Finds the nearest `girgen.toml` in `dir` or any of its parents.
+    pub fn discover(dir: &Path) -> Option<PathBuf> {
+        dir.ancestors()
+            .map(|dir| dir.join(FILE_NAME))
+            .find(|path| path.is_file())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let contents =
+            fs::read_to_string(path).map_err(|err| ConfigError::Io(path.to_owned(), err))?;
+        let mut config: Config =
+            toml::from_str(&contents).map_err(|err| ConfigError::Parse(path.to_owned(), err))?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        let resolve = |path: &mut PathBuf| *path = base.join(&*path);
+
+        config.dirs.iter_mut().for_each(resolve);
+        config
+            .files
+            .iter_mut()
+            .filter(|path| *path != Path::new(crate::STDIN))
+            .for_each(resolve);
+        config.gjs.overrides.iter_mut().for_each(resolve);
+        config.gjs.outdir.iter_mut().for_each(resolve);
+        config.gir.outdir.iter_mut().for_each(resolve);
+        config.cache.dir.iter_mut().for_each(resolve);
+
+        Ok(config)
+    }
+}
@@ -1,11 +1,45 @@
-use std::{collections, env, fs, io, path, sync};
+use super::output;
+use std::hash::Hasher;
+use std::time::{Duration, SystemTime};
+use std::{env, fs, io, path, sync};
 use twox_hash::XxHash64;
 
-type Cache = collections::HashMap<String, path::PathBuf>;
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 
 static CACHE_DIR: sync::OnceLock<Option<path::PathBuf>> = sync::OnceLock::new();
-static CACHE: sync::OnceLock<Cache> = sync::OnceLock::new();
+static POLICY: sync::OnceLock<Policy> = sync::OnceLock::new();
+
+/// Limits applied when evicting cache entries, `None` meaning unlimited.
+#[derive(Debug, Clone, Copy)]
+pub struct Policy {
+    /// Total size in bytes, the least recently used entries are evicted first
+    pub max_size: Option<u64>,
+    /// Entries not used for this long are evicted
+    pub max_age: Option<Duration>,
+    /// Evict entries written by other girgen versions
+    pub drop_other_versions: bool,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Self {
+            max_size: Some(256 * 1024 * 1024),
+            max_age: Some(Duration::from_secs(30 * 24 * 60 * 60)),
+            drop_other_versions: true,
+        }
+    }
+}
+
+/// File in the cache directory.
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub path: path::PathBuf,
+    pub size: u64,
+    /// Time of the last write or cache hit
+    pub used: SystemTime,
+    /// Version of girgen that wrote the entry
+    pub version: String,
+}
 
 fn get_cache_dir() -> Option<path::PathBuf> {
     if let Ok(p) = env::var("XDG_CACHE_HOME") {
@@ -25,37 +59,179 @@ fn get_cache_dir() -> Option<path::PathBuf> {
     }
 }
 
-fn get_cache() -> Cache {
-    CACHE_DIR
-        .get_or_init(get_cache_dir)
-        .as_ref()
-        .and_then(|dir| fs::read_dir(dir).ok())
-        .map(|dir| {
-            dir.filter_map(Result::ok)
-                .filter_map(|e| {
-                    let path = e.path();
-                    let stem = path.file_name()?.to_str()?.to_owned();
-                    Some((stem, path))
-                })
-                .collect()
-        })
-        .unwrap_or_default()
+/// Overrides the cache location, `None` disables caching.
+/// Has no effect once the cache was used.
+pub fn set_cache_dir(dir: Option<path::PathBuf>) {
+    CACHE_DIR.set(dir).ok();
+}
+
+pub fn cache_dir() -> Option<&'static path::Path> {
+    CACHE_DIR.get_or_init(get_cache_dir).as_deref()
+}
+
+/// Sets the policy used by [`evict`], has no effect once it was used.
+pub fn set_policy(policy: Policy) {
+    POLICY.set(policy).ok();
+}
+
+pub fn policy() -> Policy {
+    *POLICY.get_or_init(Policy::default)
+}
+
+/// Key of a cached output, `parts` being everything the output is derived from.
+pub fn hash(prefix: &str, name: &str, parts: &[&str]) -> String {
+    let mut hasher = XxHash64::with_seed(0);
+    for part in parts {
+        // length prefixed so that moving bytes between parts changes the hash
+        hasher.write_usize(part.len());
+        hasher.write(part.as_bytes());
+    }
+    format!("{}_{}_{}_{}", prefix, name, hasher.finish(), VERSION)
 }
 
-pub fn hash(prefix: &str, name: &str, contents: &str) -> String {
-    let hash = XxHash64::oneshot(0, contents.as_bytes());
-    format!("{}_{}_{}_{}", prefix, name, hash, VERSION)
+fn checksum(contents: &str) -> String {
+    format!("{:016x}", XxHash64::oneshot(0, contents.as_bytes()))
 }
 
+/// Stores `result` prefixed by a line with its checksum, verified by [`read_cache`].
 pub fn cache(hash: &str, result: &str) -> Result<(), io::Error> {
     if let Some(dir) = CACHE_DIR.get_or_init(get_cache_dir) {
         fs::create_dir_all(dir)?;
-        fs::write(path::PathBuf::from(dir).join(hash), result)?;
+        let contents = format!("{}\n{}", checksum(result), result);
+        output::write_atomic(dir.join(hash), contents)?;
     }
     Ok(())
 }
 
-pub fn lookup_cache<'a>(hash: &str) -> Option<&'a path::Path> {
-    let cache = CACHE.get_or_init(get_cache);
-    cache.get(hash).map(|buf| buf.as_path())
+/// Reads an entry found by [`lookup_cache`], failing with
+/// [`io::ErrorKind::InvalidData`] when it was truncated or otherwise corrupted.
+pub fn read_cache(path: &path::Path) -> Result<String, io::Error> {
+    let data = fs::read_to_string(path)?;
+
+    match data.split_once('\n') {
+        Some((sum, result)) if sum == checksum(result) => Ok(result.to_owned()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("corrupted cache entry {}", path.display()),
+        )),
+    }
+}
+
+/// Checks the filesystem on every call, so entries written since, e.g. by an
+/// earlier run in watch mode, are found as well.
+pub fn lookup_cache(hash: &str) -> Option<path::PathBuf> {
+    let path = cache_dir()?.join(hash);
+    if !path.is_file() {
+        return None;
+    }
+
+    // mark as recently used so that eviction keeps it
+    if let Ok(file) = fs::File::options().append(true).open(&path) {
+        file.set_modified(SystemTime::now()).ok();
+    }
+
+    Some(path)
+}
+
+/// Version of a file named by [`hash`], `None` for anything else so that unrelated
+/// files in a shared cache directory are never listed or removed.
+fn key_version(name: &str) -> Option<&str> {
+    if name.starts_with('.') {
+        return None;
+    }
+
+    let (rest, version) = name.rsplit_once('_')?;
+    let (rest, hash) = rest.rsplit_once('_')?;
+    let (prefix, name) = rest.split_once('_')?;
+
+    let valid_version = !version.is_empty()
+        && version
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-' | '+'));
+
+    (!prefix.is_empty() && !name.is_empty() && hash.parse::<u64>().is_ok() && valid_version)
+        .then_some(version)
+}
+
+/// Every entry in the cache directory, the least recently used first.
+/// Files not named by [`hash`] are skipped.
+pub fn entries() -> io::Result<Vec<Entry>> {
+    let Some(dir) = cache_dir() else {
+        return Ok(Vec::new());
+    };
+
+    let dir = match fs::read_dir(dir) {
+        Ok(dir) => dir,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(err),
+    };
+
+    let mut entries = Vec::new();
+    for file in dir {
+        let file = file?;
+        let name = file.file_name().to_string_lossy().into_owned();
+        let Some(version) = key_version(&name) else {
+            continue;
+        };
+
+        let meta = file.metadata()?;
+        if !meta.is_file() {
+            continue;
+        }
+
+        entries.push(Entry {
+            path: file.path(),
+            size: meta.len(),
+            used: meta.modified()?,
+            version: version.to_owned(),
+        });
+    }
+
+    entries.sort_by_key(|entry| entry.used);
+    Ok(entries)
+}
+
+/// Removes every entry, returning the removed ones.
+pub fn clean() -> io::Result<Vec<Entry>> {
+    let entries = entries()?;
+    for entry in &entries {
+        fs::remove_file(&entry.path)?;
+    }
+    Ok(entries)
+}
+
+/// Removes the entries exceeding the limits of `policy`, returning the removed ones.
+pub fn prune(policy: &Policy) -> io::Result<Vec<Entry>> {
+    let now = SystemTime::now();
+    let (mut kept, mut removed): (Vec<_>, Vec<_>) = entries()?.into_iter().partition(|entry| {
+        let outdated = policy.drop_other_versions && entry.version != VERSION;
+        let expired = policy
+            .max_age
+            .is_some_and(|max_age| now.duration_since(entry.used).unwrap_or_default() > max_age);
+        !outdated && !expired
+    });
+
+    if let Some(max_size) = policy.max_size {
+        let mut size: u64 = kept.iter().map(|entry| entry.size).sum();
+        // entries are sorted by last use, so the oldest go first
+        let evicted = kept
+            .iter()
+            .take_while(|entry| {
+                let over = size > max_size;
+                size -= entry.size;
+                over
+            })
+            .count();
+        removed.extend(kept.drain(..evicted));
+    }
+
+    for entry in &removed {
+        fs::remove_file(&entry.path)?;
+    }
+    Ok(removed)
+}
+
+/// Prunes with the policy set by [`set_policy`].
+pub fn evict() -> io::Result<Vec<Entry>> {
+    prune(&policy())
 }
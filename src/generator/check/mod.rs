@@ -0,0 +1,23 @@
+use crate::check;
+use crate::generator::{Error, Event, EventSink, Generator, Gir};
+
+/// Reports semantic problems in the parsed repositories instead of generating output.
+pub struct Check;
+
+impl Generator for Check {
+    fn generate(&self, girs: &[Gir], event: EventSink) -> Result<(), Error> {
+        if girs.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        let problems = check::validate(girs);
+        for problem in &problems {
+            event(Event::Problem { problem });
+        }
+
+        match problems.len() {
+            0 => Ok(()),
+            n => Err(Error::Invalid(n)),
+        }
+    }
+}
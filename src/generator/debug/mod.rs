@@ -1,16 +1,149 @@
-use crate::element::Repository;
-use crate::generator::{Error, Event, Generator, Gir};
+use crate::element::{Namespace, Repository};
+use crate::generator::{Error, Event, EventSink, Generator, Gir};
+use std::io::{self, Write};
 
-pub struct Debug;
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Format {
+    /// Rust debug representation
+    #[default]
+    Debug,
+    /// JSON array of repositories
+    Json,
+    /// One JSON repository per namespace on each line
+    Jsonl,
+}
+
+#[derive(Default)]
+pub struct Debug {
+    pub format: Format,
+    /// Only output namespaces matching by name and version, e.g "Gtk-4.0", or by name only
+    pub namespace: Option<String>,
+    /// Dotted path to select from the JSON representation, e.g "Gtk.Widget.methods".
+    /// Requires a JSON format.
+    pub path: Option<String>,
+}
+
+fn matches_name(value: &serde_json::Value, name: &str) -> bool {
+    let direct = value.get("name").and_then(|n| n.as_str());
+    let callable = value
+        .get("attrs")
+        .and_then(|a| a.get("name"))
+        .and_then(|n| n.as_str());
+
+    direct == Some(name) || callable == Some(name)
+}
+
+/// Resolve a segment either as a key, an index, or the name of an element
+/// in one of the child lists, e.g "Widget" in a namespace resolves to the class.
+fn select<'a>(value: &'a serde_json::Value, segment: &str) -> Option<&'a serde_json::Value> {
+    match value {
+        serde_json::Value::Object(map) => map.get(segment).or_else(|| {
+            map.values()
+                .filter_map(|v| v.as_array())
+                .flatten()
+                .find(|v| matches_name(v, segment))
+        }),
+        serde_json::Value::Array(items) => match segment.parse::<usize>() {
+            Ok(i) => items.get(i),
+            Err(_) => items.iter().find(|v| matches_name(v, segment)),
+        },
+        _ => None,
+    }
+}
+
+impl Debug {
+    fn matches_namespace(&self, namespace: &Namespace) -> bool {
+        self.namespace.as_ref().is_none_or(|filter| {
+            *filter == namespace.name
+                || *filter == format!("{}-{}", namespace.name, namespace.version)
+        })
+    }
+
+    fn values(
+        &self,
+        repos: &[&Repository],
+        event: EventSink,
+    ) -> Result<Vec<serde_json::Value>, serde_json::Error> {
+        let namespaced = repos.iter().flat_map(|repo| {
+            repo.namespaces
+                .iter()
+                .filter(|ns| self.matches_namespace(ns))
+                .map(|ns| (*repo, ns))
+        });
+
+        let Some(path) = &self.path else {
+            return namespaced
+                .map(|(repo, ns)| {
+                    let repo = Repository {
+                        namespaces: vec![ns.clone()],
+                        ..repo.clone()
+                    };
+                    serde_json::to_value(repo)
+                })
+                .collect();
+        };
+
+        let mut segments = path.split('.');
+        let root = segments.next().unwrap_or_default();
+        let segments: Vec<&str> = segments.collect();
+
+        let mut values = Vec::new();
+        for (_, ns) in namespaced.filter(|(_, ns)| ns.name == root) {
+            let value = serde_json::to_value(ns)?;
+            values.extend(
+                segments
+                    .iter()
+                    .try_fold(&value, |value, segment| select(value, segment))
+                    .cloned(),
+            );
+        }
+
+        if values.is_empty() {
+            event(Event::Warning {
+                warning: &format!("nothing found at path '{path}'"),
+            });
+        }
+
+        Ok(values)
+    }
+}
 
 impl Generator for Debug {
-    fn generate(&self, girs: &[Gir], _: fn(Event)) -> Result<(), Error> {
+    fn generate(&self, girs: &[Gir], event: EventSink) -> Result<(), Error> {
         if girs.is_empty() {
             return Err(Error::Empty);
         }
 
+        // the Rust representation cannot be queried
+        if self.format == Format::Debug && self.path.is_some() {
+            return Err(Error::FsError(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "paths can only be selected from json output",
+            )));
+        }
+
         let repos: Vec<&Repository> = girs.iter().map(|gir| &gir.repo).collect();
-        println!("{:#?}", repos);
+        let mut stdout = io::stdout().lock();
+
+        match self.format {
+            Format::Debug => {
+                let repos: Vec<&Repository> = repos
+                    .into_iter()
+                    .filter(|repo| repo.namespaces.iter().any(|ns| self.matches_namespace(ns)))
+                    .collect();
+                writeln!(stdout, "{:#?}", repos)?;
+            }
+            Format::Json => {
+                let values = self.values(&repos, event).map_err(io::Error::from)?;
+                serde_json::to_writer_pretty(&mut stdout, &values).map_err(io::Error::from)?;
+                writeln!(stdout)?;
+            }
+            Format::Jsonl => {
+                for value in self.values(&repos, event).map_err(io::Error::from)? {
+                    writeln!(stdout, "{}", value)?;
+                }
+            }
+        }
 
         Ok(())
     }
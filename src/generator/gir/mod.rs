@@ -0,0 +1,59 @@
+use crate::generator::output;
+use crate::generator::{Error, Event, EventSink, FailureKind, Generator, Gir};
+use rayon::prelude::*;
+use std::fs;
+use std::path::Path;
+
+/// Writes every parsed repository back as a normalized `.gir` file.
+pub struct GirXml {
+    pub outdir: String,
+    /// Remove the outdir before generating
+    pub clean: bool,
+}
+
+impl Generator for GirXml {
+    fn generate(&self, girs: &[Gir], event: EventSink) -> Result<(), Error> {
+        if girs.is_empty() {
+            return Err(Error::Empty);
+        }
+
+        if self.clean {
+            output::clean_outdir(Path::new(&self.outdir))?;
+        }
+        fs::create_dir_all(&self.outdir)?;
+
+        let produced: Vec<String> = girs
+            .par_iter()
+            .filter_map(|gir| {
+                let out_path = format!("{}/{}.gir", &self.outdir, gir.name);
+
+                match output::write_if_changed(&out_path, gir.repo.to_gir_xml()) {
+                    Err(err) => {
+                        event(Event::Failed {
+                            repo: Some(gir.name),
+                            symbol: None,
+                            kind: FailureKind::Io,
+                            err: err.to_string().as_str(),
+                        });
+                        None
+                    }
+                    Ok(_) => {
+                        event(Event::Generated {
+                            repo: gir.name,
+                            out_path: &out_path,
+                        });
+                        Some(format!("{}.gir", gir.name))
+                    }
+                }
+            })
+            .collect();
+
+        for path in output::prune_outdir(Path::new(&self.outdir), &produced)? {
+            event(Event::Removed {
+                out_path: &path.display().to_string(),
+            });
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use super::super::render;
 use super::{doc, gtype};
 use crate::element;
-use crate::generator::Event;
+use crate::generator::{Event, FailedSymbol, FailureKind};
 
 static TEMPLATE: &str = include_str!("../templates/callable.jinja");
 
@@ -59,13 +59,23 @@ pub fn render(_: &render::Context, args: &CallableArgs) -> Result<String, String
 
     let p_returns: Vec<_> = params
         .iter()
-        .filter(|p| matches!(p.direction.as_deref(), Some("inout" | "out")))
+        .filter(|p| {
+            matches!(
+                p.direction,
+                Some(element::Direction::InOut | element::Direction::Out)
+            )
+        })
         .cloned()
         .collect();
 
     let p_parameters: Vec<_> = params
         .iter()
-        .filter(|p| matches!(p.direction.as_deref(), None | Some("in" | "inout")))
+        .filter(|p| {
+            matches!(
+                p.direction,
+                None | Some(element::Direction::In | element::Direction::InOut)
+            )
+        })
         .cloned()
         .collect();
 
@@ -187,8 +197,10 @@ pub enum CallableElement<'a> {
     VirtualMethod(&'a element::VirtualMethod),
 }
 
+/// Renders the callables declared on `owner`, e.g "Widget".
 pub fn render_callable_elements(
     ctx: &render::Context,
+    owner: &str,
     prefix: &str,
     elements: &[CallableElement<'_>],
 ) -> Vec<String> {
@@ -225,8 +237,11 @@ pub fn render_callable_elements(
             match render(ctx, &args) {
                 Ok(res) => Some(res),
                 Err(err) => {
+                    let name = format!("{owner}.{name}");
                     (ctx.event)(Event::Failed {
-                        repo: None,
+                        repo: Some(&ctx.repo()),
+                        symbol: Some(FailedSymbol { kind, name: &name }),
+                        kind: FailureKind::Type,
                         err: &format!(
                             "failed to render {} {}-{}.{}: {}",
                             kind, ctx.namespace.name, ctx.namespace.version, name, err
@@ -1,9 +1,10 @@
 use super::super::{overrides, render};
 use super::{callable, doc, gtype};
-use crate::{element, generator::Event};
+use crate::element;
+use crate::generator::{Event, FailedSymbol, FailureKind};
 use stringcase::camel_case;
 
-fn collect_signals(ctx: &render::Context, signals: &[element::Signal]) -> Vec<String> {
+fn collect_signals(ctx: &render::Context, owner: &str, signals: &[element::Signal]) -> Vec<String> {
     signals
         .iter()
         .filter(|s| s.info.introspectable.is_none_or(|i| i))
@@ -27,11 +28,17 @@ fn collect_signals(ctx: &render::Context, signals: &[element::Signal]) -> Vec<St
             match callable::render(ctx, &args) {
                 Ok(res) => Some(res),
                 Err(err) => {
+                    let name = format!("{owner}.{}", s.name);
                     (ctx.event)(Event::Failed {
-                        repo: None,
+                        repo: Some(&ctx.repo()),
+                        symbol: Some(FailedSymbol {
+                            kind: "signal",
+                            name: &name,
+                        }),
+                        kind: FailureKind::Type,
                         err: &format!(
                             "failed to render signal {}-{}.{}: {}",
-                            ctx.namespace.name, ctx.namespace.version, s.name, err
+                            ctx.namespace.name, ctx.namespace.version, name, err
                         ),
                     });
                     None
@@ -43,6 +50,7 @@ fn collect_signals(ctx: &render::Context, signals: &[element::Signal]) -> Vec<St
 
 fn collect_properties(
     ctx: &render::Context,
+    owner: &str,
     properties: &[element::Property],
     methods: &[element::Method],
 ) -> Vec<minijinja::Value> {
@@ -76,11 +84,17 @@ fn collect_properties(
                     construct_only => matches!(p.construct_only, Some(true)),
                 }),
                 Err(err) => {
+                    let name = format!("{owner}.{}", p.name);
                     (ctx.event)(Event::Failed {
-                        repo: None,
+                        repo: Some(&ctx.repo()),
+                        symbol: Some(FailedSymbol {
+                            kind: "property",
+                            name: &name,
+                        }),
+                        kind: FailureKind::Type,
                         err: &format!(
                             "failed to render property {}-{}.{}: {}",
-                            ctx.namespace.name, ctx.namespace.version, p.name, err
+                            ctx.namespace.name, ctx.namespace.version, name, err
                         ),
                     });
                     None
@@ -139,8 +153,8 @@ impl render::Renderable<ClassContext> for element::Class {
             .cloned()
             .collect();
 
-        let signals = collect_signals(ctx, &self.signals);
-        let properties = collect_properties(ctx, &self.properties, &self.methods);
+        let signals = collect_signals(ctx, &self.name, &self.signals);
+        let properties = collect_properties(ctx, &self.name, &self.properties, &self.methods);
 
         let overrides = overrides::OVERRIDES
             .iter()
@@ -149,6 +163,7 @@ impl render::Renderable<ClassContext> for element::Class {
 
         let methods = callable::render_callable_elements(
             ctx,
+            &self.name,
             "",
             &self
                 .methods
@@ -179,6 +194,7 @@ impl render::Renderable<ClassContext> for element::Class {
 
         let constructors = callable::render_callable_elements(
             ctx,
+            &self.name,
             "",
             &ctors
                 .iter()
@@ -188,6 +204,7 @@ impl render::Renderable<ClassContext> for element::Class {
 
         let functions = callable::render_callable_elements(
             ctx,
+            &self.name,
             "",
             &self
                 .functions
@@ -198,6 +215,7 @@ impl render::Renderable<ClassContext> for element::Class {
 
         let virtual_methods = callable::render_callable_elements(
             ctx,
+            &self.name,
             "vfunc_",
             &self
                 .virtual_methods
@@ -206,16 +224,13 @@ impl render::Renderable<ClassContext> for element::Class {
                 .collect::<Vec<_>>(),
         );
 
-        let constructor_record = ctx.namespace.records.iter().find(|rec| {
-            rec.glib_is_gtype_struct_for
-                .as_ref()
-                .is_some_and(|name| name == &self.name)
-        });
+        let constructor_record = ctx.library.type_struct(ctx.namespace, &self.name);
 
         let class_functions = constructor_record
             .map(|record| {
                 callable::render_callable_elements(
                     ctx,
+                    &record.name,
                     "",
                     &record
                         .methods
@@ -232,15 +247,14 @@ impl render::Renderable<ClassContext> for element::Class {
             .unwrap_or_else(|| format!("{}Class", &self.name));
 
         let parent_class = self.parent.as_ref().map(|parent| {
-            ctx.namespace
-                .records
-                .iter()
-                .find(|rec| {
-                    rec.glib_is_gtype_struct_for
-                        .as_ref()
-                        .is_some_and(|name| name == parent)
-                })
-                .map(|rec| rec.name.clone())
+            let prefix = parent
+                .rsplit_once('.')
+                .map(|(namespace, _)| format!("{namespace}."))
+                .unwrap_or_default();
+
+            ctx.library
+                .type_struct(ctx.namespace, parent)
+                .map(|rec| format!("{prefix}{}", rec.name))
                 // TODO: check for possible name collision
                 .unwrap_or_else(|| format!("{}Class", parent))
         });
@@ -301,8 +315,8 @@ impl render::Renderable<ClassContext> for element::Interface {
             .map(String::from)
             .collect();
 
-        let signals = collect_signals(ctx, &self.signals);
-        let properties = collect_properties(ctx, &self.properties, &self.methods);
+        let signals = collect_signals(ctx, &self.name, &self.signals);
+        let properties = collect_properties(ctx, &self.name, &self.properties, &self.methods);
 
         let overrides = overrides::OVERRIDES
             .iter()
@@ -311,6 +325,7 @@ impl render::Renderable<ClassContext> for element::Interface {
 
         let methods = callable::render_callable_elements(
             ctx,
+            &self.name,
             "",
             &self
                 .methods
@@ -322,6 +337,7 @@ impl render::Renderable<ClassContext> for element::Interface {
 
         let constructors = callable::render_callable_elements(
             ctx,
+            &self.name,
             "",
             &self
                 .constructors
@@ -332,6 +348,7 @@ impl render::Renderable<ClassContext> for element::Interface {
 
         let functions = callable::render_callable_elements(
             ctx,
+            &self.name,
             "",
             &self
                 .functions
@@ -342,6 +359,7 @@ impl render::Renderable<ClassContext> for element::Interface {
 
         let virtual_methods = callable::render_callable_elements(
             ctx,
+            &self.name,
             "vfunc_",
             &self
                 .virtual_methods
@@ -350,16 +368,13 @@ impl render::Renderable<ClassContext> for element::Interface {
                 .collect::<Vec<_>>(),
         );
 
-        let constructor_record = ctx.namespace.records.iter().find(|rec| {
-            rec.glib_is_gtype_struct_for
-                .as_ref()
-                .is_some_and(|name| name == &self.name)
-        });
+        let constructor_record = ctx.library.type_struct(ctx.namespace, &self.name);
 
         let class_functions = constructor_record
             .map(|iface| {
                 callable::render_callable_elements(
                     ctx,
+                    &iface.name,
                     "",
                     &iface
                         .methods
@@ -108,7 +108,7 @@ pub fn jsdoc_with_args(args: &DocArgs) -> Result<String, String> {
 
     let in_parameters: Vec<DocParameter> = parameters
         .iter()
-        .filter(|p| matches!(p.direction.as_deref(), None | Some("in")))
+        .filter(|p| matches!(p.direction, None | Some(element::Direction::In)))
         .map(|p| DocParameter {
             name: p.name.as_deref().unwrap_or("arg"),
             text: escape_doc(&get_doc_text(&p.doc_elements)),
@@ -123,7 +123,12 @@ pub fn jsdoc_with_args(args: &DocArgs) -> Result<String, String> {
         .chain(
             parameters
                 .iter()
-                .filter(|p| matches!(p.direction.as_deref(), Some("out" | "inout")))
+                .filter(|p| {
+                    matches!(
+                        p.direction,
+                        Some(element::Direction::Out | element::Direction::InOut)
+                    )
+                })
                 .map(|p| get_doc_text(&p.doc_elements)),
         )
         .map(|s| escape_doc(&s))
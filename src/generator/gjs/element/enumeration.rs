@@ -1,6 +1,7 @@
 use super::super::render;
 use super::{callable, doc};
-use crate::{element, generator::Event};
+use crate::element;
+use crate::generator::{Event, FailedSymbol, FailureKind};
 
 const TEMPLATE: &str = include_str!("../templates/enumeration.jinja");
 
@@ -21,7 +22,12 @@ macro_rules! render_functions {
             match $ctx.namespace.c_symbol_prefixes.as_ref() {
                 None => {
                     ($ctx.event)(Event::Failed {
-                        repo: None,
+                        repo: Some(&$ctx.repo()),
+                        symbol: Some(FailedSymbol {
+                            kind: "functions",
+                            name: &$self.name,
+                        }),
+                        kind: FailureKind::MissingAttribute,
                         err: &format!(
                             "failed to render {}-{}.{} functions: Missing namespace c:symbol-prefix",
                             $ctx.namespace.name,
@@ -59,7 +65,12 @@ macro_rules! render_functions {
                                 Ok(res) => Some(res),
                                 Err(err) => {
                                     ($ctx.event)(Event::Failed {
-                                        repo: None,
+                                        repo: Some(&$ctx.repo()),
+                                        symbol: Some(FailedSymbol {
+                                            kind: "function",
+                                            name: &format!("{}.{}", $self.name, f.attrs.name),
+                                        }),
+                                        kind: FailureKind::Type,
                                         err: &format!(
                                             "failed to render {}-{}.{}.{} function: {}",
                                             $ctx.namespace.name,
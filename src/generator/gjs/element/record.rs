@@ -16,6 +16,7 @@ pub struct FieldContext {
 
 macro_rules! ctx {
     ($ns:expr, $self:expr, $name:expr, $ctx:expr) => {{
+        let name: String = $name;
         let jsdoc = doc::jsdoc(&$self.info_elements, &$self.info)?;
 
         let fields: Vec<FieldContext> = $self
@@ -42,6 +43,7 @@ macro_rules! ctx {
 
         let methods = callable::render_callable_elements(
             $ctx,
+            &name,
             "",
             &$self
                 .methods
@@ -52,6 +54,7 @@ macro_rules! ctx {
 
         let constructors = callable::render_callable_elements(
             $ctx,
+            &name,
             "",
             &$self
                 .constructors
@@ -62,6 +65,7 @@ macro_rules! ctx {
 
         let functions = callable::render_callable_elements(
             $ctx,
+            &name,
             "",
             &$self
                 .functions
@@ -73,7 +77,7 @@ macro_rules! ctx {
         Ok(RecordContext {
             namespace: $ns.clone(),
             jsdoc,
-            name: $name,
+            name,
             bag_constructor: false,
             constructor: None,
             fields,
@@ -3,75 +3,269 @@ mod gjs_lib;
 mod overrides;
 mod render;
 
-use super::cache;
-use crate::element::Repository;
-use crate::generator::{Error, Event, Generator, Gir};
+use super::{cache, output};
+use crate::generator::{Error, Event, EventSink, FailedSymbol, FailureKind, Generator, Gir};
+use crate::library::Library;
 use rayon::prelude::*;
-use std::{collections::HashMap, fs};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::{fs, io};
+
+/// Event emitted while rendering a repository. These are stored with its cache entry
+/// and emitted again on a cache hit, so that failures are reported on every run.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+enum Recorded {
+    Failed {
+        repo: Option<String>,
+        /// Kind and name of the symbol
+        symbol: Option<(String, String)>,
+        kind: FailureKind,
+        err: String,
+    },
+    Rendered {
+        namespace: String,
+        symbols: usize,
+        skipped: usize,
+    },
+}
+
+impl Recorded {
+    fn new(event: &Event) -> Option<Self> {
+        match event {
+            Event::Failed {
+                repo,
+                symbol,
+                kind,
+                err,
+            } => Some(Self::Failed {
+                repo: repo.map(str::to_owned),
+                symbol: symbol.map(|s| (s.kind.to_owned(), s.name.to_owned())),
+                kind: *kind,
+                err: err.to_string(),
+            }),
+            Event::Rendered {
+                namespace,
+                symbols,
+                skipped,
+            } => Some(Self::Rendered {
+                namespace: namespace.to_string(),
+                symbols: *symbols,
+                skipped: *skipped,
+            }),
+            _ => None,
+        }
+    }
+
+    fn replay(&self, event: EventSink) {
+        match self {
+            Self::Failed {
+                repo,
+                symbol,
+                kind,
+                err,
+            } => event(Event::Failed {
+                repo: repo.as_deref(),
+                symbol: symbol
+                    .as_ref()
+                    .map(|(kind, name)| FailedSymbol { kind, name }),
+                kind: *kind,
+                err,
+            }),
+            Self::Rendered {
+                namespace,
+                symbols,
+                skipped,
+            } => event(Event::Rendered {
+                namespace,
+                symbols: *symbols,
+                skipped: *skipped,
+            }),
+        }
+    }
+}
+
+/// Cache entry of a repository: a line with the recorded events, then its declarations.
+/// Returns `None` for entries written in another format, which are rendered again.
+fn split_entry(entry: &str) -> Option<(Vec<Recorded>, &str)> {
+    let (events, dts) = entry.split_once('\n')?;
+    Some((serde_json::from_str(events).ok()?, dts))
+}
 
 pub struct TypeScript {
     pub outdir: String,
     pub alias: bool,
+    /// Files named after a namespace, e.g "Gtk-4.0.d.ts", appended to its declarations
+    pub overrides: Vec<PathBuf>,
+    /// Remove the outdir before generating
+    pub clean: bool,
+}
+
+impl TypeScript {
+    /// Key covering everything the declarations of a repository are rendered from:
+    /// its contents, the contents of every repository it imports, the generator
+    /// options and both the builtin and user overrides of its namespaces.
+    fn cache_key(
+        &self,
+        gir: &Gir,
+        library: &Library,
+        contents: &HashMap<&str, &str>,
+        user_overrides: &HashMap<String, String>,
+    ) -> String {
+        let imports: Vec<String> = gir
+            .repo
+            .find_imports(library)
+            .iter()
+            .map(|inc| format!("{}-{}", inc.name, inc.version))
+            .collect();
+
+        let options = format!("alias={}", self.alias);
+
+        let overrides: Vec<String> = gir
+            .repo
+            .namespaces
+            .iter()
+            .filter_map(|ns| {
+                overrides::OVERRIDES
+                    .iter()
+                    .find(|o| o.namespace == ns.name && o.version == ns.version)
+            })
+            .map(|o| format!("{o:?}"))
+            .collect();
+
+        let mut parts = vec![gir.contents.as_str(), &options];
+        for name in &imports {
+            parts.push(name);
+            parts.push(contents.get(name.as_str()).copied().unwrap_or_default());
+        }
+        parts.extend(overrides.iter().map(String::as_str));
+        parts.extend(user_overrides.get(gir.name).map(String::as_str));
+
+        cache::hash("ts_", gir.name, &parts)
+    }
 }
 
 impl Generator for TypeScript {
-    fn generate(&self, girs: &[Gir], event: fn(Event)) -> Result<(), Error> {
+    fn generate(&self, girs: &[Gir], event: EventSink) -> Result<(), Error> {
         if girs.is_empty() {
             return Err(Error::Empty);
         }
 
+        if self.clean {
+            output::clean_outdir(Path::new(&self.outdir))?;
+        }
         fs::create_dir_all(&self.outdir)?;
 
-        let repos: Vec<&Repository> = girs.iter().map(|gir| &gir.repo).collect();
+        let library = Library::new(girs);
+
+        let namespaces: HashSet<String> = girs
+            .iter()
+            .flat_map(|gir| &gir.repo.namespaces)
+            .map(|ns| format!("{}-{}", ns.name, ns.version))
+            .collect();
+
+        let mut user_overrides = HashMap::new();
+        for path in &self.overrides {
+            let Some(name) = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.strip_suffix(".d.ts"))
+                .filter(|name| name.contains('-'))
+            else {
+                return Err(Error::FsError(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "override {} is not named after a namespace, e.g \"Gtk-4.0.d.ts\"",
+                        path.display()
+                    ),
+                )));
+            };
+            if !namespaces.contains(name) {
+                event(Event::Warning {
+                    warning: &format!(
+                        "override {} is not used, {name} is not loaded",
+                        path.display()
+                    ),
+                });
+            }
+            user_overrides.insert(name.to_owned(), fs::read_to_string(path)?);
+        }
+
+        let contents: HashMap<&str, &str> = girs
+            .iter()
+            .map(|gir| (gir.name, gir.contents.as_str()))
+            .collect();
 
         let valid_girs: Vec<&Gir> = girs
             .par_iter()
             .filter_map(|gir| {
-                let hash = cache::hash("ts_", gir.name, &gir.contents);
+                let hash = self.cache_key(gir, &library, &contents, &user_overrides);
                 let cache_path = cache::lookup_cache(&hash);
                 let out_path = format!("{}/{}.d.ts", &self.outdir, gir.name);
 
                 if let Some(path) = cache_path {
-                    match fs::read_to_string(path) {
+                    match cache::read_cache(&path) {
                         Err(err) => event(Event::Warning {
                             warning: err.to_string().as_str(),
                         }),
-                        Ok(result) => match fs::write(&out_path, result) {
-                            Err(err) => event(Event::Warning {
-                                warning: err.to_string().as_str(),
-                            }),
-                            Ok(_) => {
-                                event(Event::CacheHit {
-                                    repo: gir.name,
-                                    out_path: &out_path,
-                                });
-                                return Some(gir);
-                            }
+                        Ok(entry) => match split_entry(&entry) {
+                            None => {}
+                            Some((recorded, dts)) => match output::write_if_changed(&out_path, dts)
+                            {
+                                Err(err) => event(Event::Warning {
+                                    warning: err.to_string().as_str(),
+                                }),
+                                Ok(_) => {
+                                    for e in &recorded {
+                                        e.replay(event);
+                                    }
+                                    event(Event::CacheHit {
+                                        repo: gir.name,
+                                        out_path: &out_path,
+                                    });
+                                    return Some(gir);
+                                }
+                            },
                         },
                     }
                 }
 
-                let result = match gir.repo.generate_dts(&repos, event) {
+                let recorded = Mutex::new(Vec::new());
+                let recording = |e: Event| {
+                    if let Some(r) = Recorded::new(&e) {
+                        recorded.lock().unwrap().push(r);
+                    }
+                    event(e)
+                };
+
+                let result = match gir.repo.generate_dts(&library, &user_overrides, &recording) {
                     Ok(result) => result,
                     Err(err) => {
                         event(Event::Failed {
                             repo: Some(gir.name),
+                            symbol: None,
+                            kind: FailureKind::Template,
                             err: err.as_str(),
                         });
                         return None;
                     }
                 };
 
-                if let Err(err) = cache::cache(&hash, &result) {
+                let recorded = serde_json::to_string(&recorded.into_inner().unwrap())
+                    .expect("recorded events serialize");
+                if let Err(err) = cache::cache(&hash, &format!("{recorded}\n{result}")) {
                     event(Event::Warning {
                         warning: err.to_string().as_str(),
                     })
                 }
 
-                match fs::write(&out_path, &result) {
+                match output::write_if_changed(&out_path, &result) {
                     Err(err) => {
                         event(Event::Failed {
                             repo: Some(gir.name),
+                            symbol: None,
+                            kind: FailureKind::Io,
                             err: err.to_string().as_str(),
                         });
                         None
@@ -91,7 +285,7 @@ impl Generator for TypeScript {
             .par_iter()
             .map(|lib| {
                 let path = format!("{}/{}.d.ts", &self.outdir, lib.name);
-                fs::write(&path, lib.content).expect("Failed to write file");
+                output::write_if_changed(&path, lib.content).expect("Failed to write file");
                 event(Event::CacheHit {
                     repo: lib.name,
                     out_path: &path,
@@ -118,7 +312,7 @@ impl Generator for TypeScript {
                 )
                 .unwrap();
 
-            fs::write(format!("{}/aliases.d.ts", &self.outdir), aliases)?;
+            output::write_if_changed(format!("{}/aliases.d.ts", &self.outdir), aliases)?;
         }
 
         let index = minijinja::Environment::new()
@@ -133,19 +327,44 @@ impl Generator for TypeScript {
             .unwrap();
 
         let index_path = format!("{}/index.d.ts", &self.outdir);
-        fs::write(&index_path, index)?;
+        output::write_if_changed(&index_path, index)?;
         event(Event::Generated {
             repo: "index",
             out_path: index_path.as_str(),
         });
 
         let package_path = format!("{}/package.json", &self.outdir);
-        fs::write(&package_path, include_str!("./gjs_lib/package.json"))?;
+        output::write_if_changed(&package_path, include_str!("./gjs_lib/package.json"))?;
         event(Event::CacheHit {
             repo: "package",
             out_path: package_path.as_str(),
         });
 
+        let produced: Vec<String> = valid_girs
+            .iter()
+            .map(|gir| gir.name)
+            .chain(imports)
+            .map(|name| format!("{name}.d.ts"))
+            .chain(self.alias.then(|| "aliases.d.ts".to_owned()))
+            .chain(["index.d.ts".to_owned(), "package.json".to_owned()])
+            .collect();
+
+        for path in output::prune_outdir(Path::new(&self.outdir), &produced)? {
+            event(Event::Removed {
+                out_path: &path.display().to_string(),
+            });
+        }
+
+        if let Err(err) = cache::evict() {
+            event(Event::Warning {
+                warning: &format!("failed to evict cache entries: {err}"),
+            });
+        }
+
         Ok(())
     }
+
+    fn extra_inputs(&self) -> Vec<PathBuf> {
+        self.overrides.clone()
+    }
 }
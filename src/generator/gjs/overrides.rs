@@ -1,8 +1,10 @@
+#[derive(Debug)]
 pub struct ClassOverride<'a> {
     pub name: &'a str,
     pub methods: &'a [&'a str],
 }
 
+#[derive(Debug)]
 pub struct Override<'a> {
     pub namespace: &'a str,
     pub version: &'a str,
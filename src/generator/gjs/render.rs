@@ -1,11 +1,25 @@
 use super::overrides;
-use crate::{element, generator::Event};
+use crate::{
+    element,
+    generator::{Event, EventSink, FailedSymbol, FailureKind},
+    library::Library,
+};
 use rayon::prelude::*;
 use rayon::scope;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 pub struct Context<'a> {
     pub namespace: &'a element::Namespace,
-    pub event: fn(Event),
+    pub library: &'a Library<'a>,
+    pub event: EventSink<'a>,
+}
+
+impl Context<'_> {
+    /// Name and version of the rendered namespace, e.g "Gtk-4.0"
+    pub fn repo(&self) -> String {
+        format!("{}-{}", self.namespace.name, self.namespace.version)
+    }
 }
 
 fn escape_member(name: &str) -> String {
@@ -39,19 +53,22 @@ pub trait Renderable<T: serde::Serialize> {
         minijinja::Environment::new()
     }
 
-    fn render(&self, g_ctx: &Context) -> Result<String, String> {
+    fn render(&self, g_ctx: &Context) -> Result<String, (FailureKind, String)> {
         let name = self.name(g_ctx);
 
         let ctx = match self.ctx(g_ctx) {
             Ok(ctx) => ctx,
             Err(err) => {
-                return Err(format!(
-                    "rendering {} {}-{}.{}: {}",
-                    Self::KIND,
-                    g_ctx.namespace.name,
-                    g_ctx.namespace.version,
-                    name,
-                    err
+                return Err((
+                    FailureKind::Type,
+                    format!(
+                        "rendering {} {}-{}.{}: {}",
+                        Self::KIND,
+                        g_ctx.namespace.name,
+                        g_ctx.namespace.version,
+                        name,
+                        err
+                    ),
                 ));
             }
         };
@@ -62,13 +79,16 @@ pub trait Renderable<T: serde::Serialize> {
         env.add_filter("escape_toplevel", escape_toplevel);
 
         env.render_str(Self::TEMPLATE, &ctx).map_err(|err| {
-            format!(
-                "rendering {} {}-{}.{}: {:?}",
-                Self::KIND,
-                g_ctx.namespace.name,
-                g_ctx.namespace.version,
-                self.name(g_ctx),
-                err
+            (
+                FailureKind::Template,
+                format!(
+                    "rendering {} {}-{}.{}: {:?}",
+                    Self::KIND,
+                    g_ctx.namespace.name,
+                    g_ctx.namespace.version,
+                    self.name(g_ctx),
+                    err
+                ),
             )
         })
     }
@@ -92,9 +112,14 @@ fn render<R: serde::Serialize, T: Renderable<R> + Sync>(items: &[T], ctx: &Conte
 
             match elem.render(ctx) {
                 Ok(elem) => Some(elem),
-                Err(err) => {
+                Err((kind, err)) => {
                     (ctx.event)(Event::Failed {
-                        repo: None,
+                        repo: Some(&ctx.repo()),
+                        symbol: Some(FailedSymbol {
+                            kind: T::KIND,
+                            name: elem.name(ctx),
+                        }),
+                        kind,
                         err: err.as_str(),
                     });
                     None
@@ -108,7 +133,7 @@ fn render<R: serde::Serialize, T: Renderable<R> + Sync>(items: &[T], ctx: &Conte
 struct RenderedNamespace<'a> {
     name: &'a str,
     version: &'a str,
-    extra_content: &'a str,
+    extra_content: String,
     aliases: Vec<String>,
     classes: Vec<String>,
     interfaces: Vec<String>,
@@ -121,7 +146,46 @@ struct RenderedNamespace<'a> {
     constants: Vec<String>,
 }
 
-fn render_namespace<'a>(ctx: Context<'a>) -> RenderedNamespace<'a> {
+impl RenderedNamespace<'_> {
+    fn symbols(&self) -> usize {
+        [
+            &self.aliases,
+            &self.classes,
+            &self.interfaces,
+            &self.records,
+            &self.enums,
+            &self.functions,
+            &self.unions,
+            &self.bitfields,
+            &self.callbacks,
+            &self.constants,
+        ]
+        .iter()
+        .map(|items| items.len())
+        .sum()
+    }
+}
+
+fn render_namespace<'a>(
+    namespace: &'a element::Namespace,
+    library: &Library,
+    user_override: Option<&str>,
+    event: EventSink,
+) -> RenderedNamespace<'a> {
+    // every failure skips a symbol or one of its members
+    let skipped = AtomicUsize::new(0);
+    let counted = |e: Event| {
+        if let Event::Failed { .. } = e {
+            skipped.fetch_add(1, Ordering::Relaxed);
+        }
+        event(e)
+    };
+    let ctx = Context {
+        namespace,
+        library,
+        event: &counted,
+    };
+
     let overrides = overrides::OVERRIDES
         .iter()
         .find(|o| o.namespace == ctx.namespace.name && o.version == ctx.namespace.version);
@@ -150,10 +214,15 @@ fn render_namespace<'a>(ctx: Context<'a>) -> RenderedNamespace<'a> {
         s.spawn(|_| constants = Some(render(&ctx.namespace.constants, &ctx)));
     });
 
-    RenderedNamespace {
-        name: &ctx.namespace.name,
-        version: &ctx.namespace.version,
-        extra_content: overrides.and_then(|o| o.content).unwrap_or_default(),
+    let rendered = RenderedNamespace {
+        name: &namespace.name,
+        version: &namespace.version,
+        extra_content: overrides
+            .and_then(|o| o.content)
+            .into_iter()
+            .chain(user_override)
+            .collect::<Vec<_>>()
+            .join("\n"),
         aliases: aliases.unwrap(),
         classes: classes.unwrap(),
         interfaces: interfaces.unwrap(),
@@ -164,7 +233,15 @@ fn render_namespace<'a>(ctx: Context<'a>) -> RenderedNamespace<'a> {
         bitfields: bitfields.unwrap(),
         callbacks: callbacks.unwrap(),
         constants: constants.unwrap(),
-    }
+    };
+
+    event(Event::Rendered {
+        namespace: &format!("{}-{}", namespace.name, namespace.version),
+        symbols: rendered.symbols(),
+        skipped: skipped.into_inner(),
+    });
+
+    rendered
 }
 
 #[derive(serde::Serialize)]
@@ -174,8 +251,8 @@ struct Import<'a> {
 }
 
 impl element::Repository {
-    fn find_imports(&self, repos: &[&element::Repository]) -> Vec<element::Include> {
-        let mut includes = self.find_includes(repos);
+    pub(super) fn find_imports(&self, library: &Library) -> Vec<element::Include> {
+        let mut includes = library.includes(self);
 
         let namespace = self
             .namespaces
@@ -208,18 +285,24 @@ impl element::Repository {
         includes
     }
 
+    /// `user_overrides` maps a name and version like "Gtk-4.0" to extra declarations
     pub fn generate_dts(
         &self,
-        repos: &[&element::Repository],
-        event: fn(Event),
+        library: &Library,
+        user_overrides: &HashMap<String, String>,
+        event: EventSink,
     ) -> Result<String, String> {
         let namespaces = self
             .namespaces
             .par_iter()
-            .map(|namespace| render_namespace(Context { namespace, event }))
+            .map(|namespace| {
+                let key = format!("{}-{}", namespace.name, namespace.version);
+                let user_override = user_overrides.get(&key).map(String::as_str);
+                render_namespace(namespace, library, user_override, event)
+            })
             .collect::<Vec<_>>();
 
-        let includes = self.find_imports(repos);
+        let includes = self.find_imports(library);
         let imports: Vec<Import> = includes
             .iter()
             .map(|inc| Import {
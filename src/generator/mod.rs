@@ -1,19 +1,52 @@
-mod cache;
+pub mod cache;
+pub mod check;
 pub mod debug;
+pub mod gir;
 pub mod gjs;
+mod output;
 
-pub use cache::{cache, hash, lookup_cache};
+pub use cache::{cache, hash, lookup_cache, read_cache, set_cache_dir};
 
-use crate::element;
+use crate::check::Problem;
+use crate::{element, error};
+use serde_json::json;
 use std::{io, path};
 
+/// Cause of a [`Event::ParseFailed`] or [`Event::Failed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum FailureKind {
+    /// Reading or writing a file failed
+    Io,
+    /// The GIR or typelib is malformed
+    Parse,
+    /// A type could not be resolved or is not supported
+    Type,
+    /// An element lacks an attribute needed for rendering
+    MissingAttribute,
+    /// The output template failed to render
+    Template,
+}
+
+/// Symbol a failure occurred on, e.g kind "method" and name "Widget.show".
+#[derive(Debug, Clone, Copy)]
+pub struct FailedSymbol<'a> {
+    pub kind: &'a str,
+    pub name: &'a str,
+}
+
 pub enum Event<'a> {
     Parsed {
+        /// Name the repository is generated as, e.g "Gtk-4.0"
+        repo: &'a str,
         file_path: &'a path::Path,
+        events: &'a [error::ParseEvent],
     },
     ParseFailed {
         file_path: &'a path::Path,
+        kind: FailureKind,
         err: &'a str,
+        location: Option<&'a error::Location>,
     },
     Ignored {
         file_path: &'a path::Path,
@@ -22,8 +55,13 @@ pub enum Event<'a> {
     Warning {
         warning: &'a str,
     },
+    Problem {
+        problem: &'a Problem,
+    },
     Failed {
         repo: Option<&'a str>,
+        symbol: Option<FailedSymbol<'a>>,
+        kind: FailureKind,
         err: &'a str,
     },
     Generated {
@@ -34,11 +72,142 @@ pub enum Event<'a> {
         repo: &'a str,
         out_path: &'a str,
     },
+    /// File from a previous run that is no longer generated was deleted
+    Removed {
+        out_path: &'a str,
+    },
+    /// A namespace was rendered, `skipped` counts the symbols and members that failed
+    Rendered {
+        namespace: &'a str,
+        symbols: usize,
+        skipped: usize,
+    },
+}
+
+fn file_stem(path: &path::Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+fn location_json(location: Option<&error::Location>) -> serde_json::Value {
+    match location {
+        Some(location) => json!({
+            "line": location.line,
+            "column": location.column,
+            "element": location.path,
+        }),
+        None => serde_json::Value::Null,
+    }
+}
+
+impl Event<'_> {
+    /// Structured form of the event for tooling, one object per event.
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Event::Parsed {
+                repo,
+                file_path,
+                events,
+            } => json!({
+                "event": "parsed",
+                "repo": repo,
+                "path": file_path,
+                "skipped": events
+                    .iter()
+                    .map(|event| json!({
+                        "message": event.to_string(),
+                        "location": location_json(Some(event.location())),
+                    }))
+                    .collect::<Vec<_>>(),
+            }),
+            Event::ParseFailed {
+                file_path,
+                kind,
+                err,
+                location,
+            } => json!({
+                "event": "parse-failed",
+                "repo": file_stem(file_path),
+                "path": file_path,
+                "error_kind": kind,
+                "message": err,
+                "location": location_json(*location),
+            }),
+            Event::Ignored { file_path, cause } => json!({
+                "event": "ignored",
+                "repo": file_stem(file_path),
+                "path": file_path,
+                "message": cause,
+            }),
+            Event::Warning { warning } => json!({
+                "event": "warning",
+                "message": warning,
+            }),
+            Event::Problem { problem } => json!({
+                "event": "problem",
+                "repo": problem.repo,
+                "symbol": problem.path,
+                "message": problem.kind.to_string(),
+            }),
+            Event::Failed {
+                repo,
+                symbol,
+                kind,
+                err,
+            } => json!({
+                "event": "failed",
+                "repo": repo,
+                "symbol_kind": symbol.map(|s| s.kind),
+                "symbol": symbol.map(|s| s.name),
+                "error_kind": kind,
+                "message": err,
+            }),
+            Event::Generated { repo, out_path } => json!({
+                "event": "generated",
+                "repo": repo,
+                "path": out_path,
+            }),
+            Event::CacheHit { repo, out_path } => json!({
+                "event": "cache-hit",
+                "repo": repo,
+                "path": out_path,
+            }),
+            Event::Removed { out_path } => json!({
+                "event": "removed",
+                "path": out_path,
+            }),
+            Event::Rendered {
+                namespace,
+                symbols,
+                skipped,
+            } => json!({
+                "event": "rendered",
+                "repo": namespace,
+                "symbols": symbols,
+                "skipped": skipped,
+            }),
+        }
+    }
 }
 
 pub enum Error {
     Empty,
     FsError(io::Error),
+    /// Validation found the given number of problems
+    Invalid(usize),
+    /// Requested or included namespace that was not found in any dir
+    Missing {
+        namespace: String,
+        included_by: Option<String>,
+    },
+    /// Requested or included namespace that was left out by an ignore or include pattern
+    Filtered {
+        namespace: String,
+        included_by: Option<String>,
+        /// Why it was left out, e.g "matches ignore pattern 'GLib-*'"
+        cause: String,
+    },
 }
 
 impl From<io::Error> for Error {
@@ -53,6 +222,16 @@ pub struct Gir<'a> {
     pub repo: element::Repository,
 }
 
+/// Receives events from parsing and generating. Called from multiple threads at once,
+/// so closures capturing state need to synchronize it, e.g with a `Mutex`.
+pub type EventSink<'a> = &'a (dyn Fn(Event) + Sync);
+
 pub trait Generator {
-    fn generate(&self, girs: &[Gir], event: fn(Event)) -> Result<(), Error>;
+    fn generate(&self, girs: &[Gir], event: EventSink) -> Result<(), Error>;
+
+    /// Files read besides the repositories, e.g overrides. In watch mode the generator
+    /// runs again when one of them changes.
+    fn extra_inputs(&self) -> Vec<path::PathBuf> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,88 @@
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::{fs, io, process};
+
+/// Writes through a temporary file in the same directory which is then renamed over
+/// `path`, so an interrupted write never leaves a truncated file behind.
+pub fn write_atomic(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    let path = path.as_ref();
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let tmp = path.with_file_name(format!(".{}.{}.tmp", name, process::id()));
+
+    fs::write(&tmp, contents)
+        .and_then(|_| fs::rename(&tmp, path))
+        .inspect_err(|_| {
+            fs::remove_file(&tmp).ok();
+        })
+}
+
+/// Like [`write_atomic`] but leaves the file untouched when it already has `contents`,
+/// keeping its modification time. Returns whether the file was written.
+pub fn write_if_changed(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> io::Result<bool> {
+    let path = path.as_ref();
+    let contents = contents.as_ref();
+
+    match fs::read(path) {
+        Ok(existing) if existing == contents => Ok(false),
+        _ => write_atomic(path, contents).map(|_| true),
+    }
+}
+
+/// Lists the files written to an output directory, one name per line, so that
+/// later runs know which files they own.
+pub const MANIFEST: &str = ".girgen-manifest";
+
+fn read_manifest(dir: &Path) -> io::Result<Option<BTreeSet<String>>> {
+    match fs::read_to_string(dir.join(MANIFEST)) {
+        Ok(manifest) => Ok(Some(manifest.lines().map(str::to_owned).collect())),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(err) => Err(err),
+    }
+}
+
+/// Removes the output directory. Refuses to when it has contents but no manifest,
+/// as then it was not created by girgen.
+pub fn clean_outdir(dir: &Path) -> io::Result<()> {
+    let has_contents = match fs::read_dir(dir) {
+        Ok(mut entries) => entries.next().is_some(),
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(err),
+    };
+
+    if has_contents && read_manifest(dir)?.is_none() {
+        return Err(io::Error::other(format!(
+            "refusing to clean {}, it has no {} and might not be generated",
+            dir.display(),
+            MANIFEST
+        )));
+    }
+
+    fs::remove_dir_all(dir)
+}
+
+/// Removes the files a previous run wrote that are not in `produced` and records
+/// `produced` as the new manifest. Returns the removed files.
+pub fn prune_outdir(dir: &Path, produced: &[String]) -> io::Result<Vec<PathBuf>> {
+    let produced: BTreeSet<String> = produced.iter().cloned().collect();
+    let mut removed = Vec::new();
+
+    for name in read_manifest(dir)?.unwrap_or_default() {
+        // only plain file names are accepted so that a tampered manifest
+        // cannot point outside the directory
+        if produced.contains(&name) || name.contains(['/', '\\']) || name.starts_with('.') {
+            continue;
+        }
+
+        let path = dir.join(&name);
+        match fs::remove_file(&path) {
+            Ok(_) => removed.push(path),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+            Err(err) => return Err(err),
+        }
+    }
+
+    let manifest: String = produced.iter().map(|name| format!("{name}\n")).collect();
+    write_if_changed(dir.join(MANIFEST), manifest)?;
+
+    Ok(removed)
+}
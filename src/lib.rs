@@ -1,11 +1,24 @@
 mod parser;
 
+pub mod check;
+pub mod config;
+
 pub mod generator;
-pub use parser::{element, error, parse_gir};
+pub mod library;
+pub mod pattern;
+pub mod report;
+mod watch;
+pub use parser::{element, error, parse_gir, parse_typelib};
+pub use watch::watch;
 
-use generator::{Error, Event, Generator, Gir};
+use generator::{Error, Event, EventSink, FailureKind, Generator, Gir};
+use pattern::Pattern;
 use rayon::prelude::*;
+use report::Report;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::{env, fs};
 
 pub fn default_dirs() -> Vec<PathBuf> {
@@ -35,14 +48,349 @@ pub fn default_dirs() -> Vec<PathBuf> {
     dirs
 }
 
-pub struct Args<G: Generator + Sync> {
+fn is_typelib(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "typelib")
+}
+
+enum FileError {
+    Io(std::io::Error),
+    Parse(error::ParseError),
+}
+
+type Parsed = (element::Repository, String, Vec<error::ParseEvent>);
+
+/// Parse GIR or typelib data, typelibs are converted to GIR so the contents can be hashed the same way.
+fn parse_data(
+    data: Vec<u8>,
+    typelib: bool,
+    options: &parse_gir::ParseOptions,
+) -> Result<Parsed, FileError> {
+    if typelib {
+        let repo = parse_typelib::parse(&data).map_err(FileError::Parse)?;
+        let contents = repo.to_gir_xml();
+        return Ok((repo, contents, Vec::new()));
+    }
+
+    let contents = String::from_utf8(data).map_err(|err| FileError::Parse(err.into()))?;
+    let (repo, events) =
+        parse_gir::parse_with_options(&contents, options).map_err(FileError::Parse)?;
+    Ok((repo, contents, events))
+}
+
+fn parse_file(path: &Path, options: &parse_gir::ParseOptions) -> Result<Parsed, FileError> {
+    let data = fs::read(path).map_err(FileError::Io)?;
+    parse_data(data, is_typelib(path), options)
+}
+
+/// Path used for reading from stdin in [`Args::files`].
+pub const STDIN: &str = "-";
+
+pub struct Args<'a, G: Generator + Sync> {
     pub dirs: Vec<PathBuf>,
-    pub ignore: Vec<String>,
-    pub on_event: fn(Event),
+    /// Skip namespaces whose file stem matches any of these, e.g "Gtk-3.*"
+    pub ignore: Vec<Pattern>,
+    /// When not empty, skip namespaces whose file stem matches none of these
+    pub include: Vec<Pattern>,
+    /// Files loaded before the dirs, named after their namespace and version and
+    /// taking precedence over discovered files of the same namespace-version.
+    /// [`STDIN`] reads a GIR or typelib from stdin.
+    pub files: Vec<PathBuf>,
+    /// Only load these namespaces and everything they include, e.g "Gtk-4.0"
+    pub only: Vec<String>,
+    pub parse_options: parse_gir::ParseOptions,
+    /// Called for every parse and generate event, possibly from multiple threads
+    pub on_event: EventSink<'a>,
     pub generator: G,
 }
 
-pub fn girgen<T: Generator + Sync>(args: Args<T>) -> Result<(), Error> {
+/// Reports a file that failed to parse, see [`name_parsed`] for the ones that did.
+fn report<T: Generator + Sync>(
+    path: &Path,
+    parsed: Result<Parsed, FileError>,
+    args: &Args<'_, T>,
+) -> Option<Parsed> {
+    match parsed {
+        Ok(parsed) => Some(parsed),
+        Err(FileError::Io(err)) => {
+            (args.on_event)(Event::ParseFailed {
+                file_path: path,
+                kind: FailureKind::Io,
+                err: err.to_string().as_str(),
+                location: None,
+            });
+            None
+        }
+        Err(FileError::Parse(err)) => {
+            (args.on_event)(Event::ParseFailed {
+                file_path: path,
+                kind: FailureKind::Parse,
+                err: err.to_string().as_str(),
+                location: err.location(),
+            });
+            None
+        }
+    }
+}
+
+fn load<'a, T: Generator + Sync>(path: &'a Path, args: &Args<'_, T>) -> Option<Gir<'a>> {
+    let parsed = report(path, parse_file(path, &args.parse_options), args);
+    let (_, repo, contents) = name_parsed(path, parsed, false, args)?;
+    Some(Gir {
+        name: path.file_stem().and_then(|f| f.to_str()).unwrap(),
+        repo,
+        contents,
+    })
+}
+
+/// Names a parsed file and reports it. Explicit inputs are named after their first
+/// namespace and version, `<namespace>-<version>`, discovered files after their stem.
+fn name_parsed<T: Generator + Sync>(
+    path: &Path,
+    parsed: Option<Parsed>,
+    explicit: bool,
+    args: &Args<'_, T>,
+) -> Option<(String, element::Repository, String)> {
+    let (repo, contents, events) = parsed?;
+
+    let name = if explicit {
+        let Some(ns) = repo.namespaces.first() else {
+            (args.on_event)(Event::ParseFailed {
+                file_path: path,
+                kind: FailureKind::Parse,
+                err: "missing namespace",
+                location: None,
+            });
+            return None;
+        };
+        format!("{}-{}", ns.name, ns.version)
+    } else {
+        path.file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    };
+
+    (args.on_event)(Event::Parsed {
+        repo: &name,
+        file_path: path,
+        events: &events,
+    });
+
+    Some((name, repo, contents))
+}
+
+fn read_stdin<T: Generator + Sync>(args: &Args<'_, T>) -> Option<Parsed> {
+    let path = Path::new(STDIN);
+    let mut data = Vec::new();
+
+    let parsed = match io::stdin().read_to_end(&mut data) {
+        Ok(_) => {
+            let typelib = data.starts_with(parse_typelib::MAGIC);
+            parse_data(data, typelib, &args.parse_options)
+        }
+        Err(err) => Err(FileError::Io(err)),
+    };
+
+    report(path, parsed, args)
+}
+
+/// Parsed explicit file or stdin, see [`Args::files`].
+struct Explicit {
+    path: PathBuf,
+    name: String,
+    repo: element::Repository,
+    contents: String,
+}
+
+/// Parses the explicit files and stdin, in the order they were given.
+fn load_explicit<T: Generator + Sync>(args: &Args<'_, T>) -> Vec<Explicit> {
+    args.files
+        .par_iter()
+        .map(|path| match path == Path::new(STDIN) {
+            true => None,
+            false => report(path, parse_file(path, &args.parse_options), args),
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .zip(&args.files)
+        .filter_map(|(parsed, path)| {
+            let parsed = match path == Path::new(STDIN) {
+                true => read_stdin(args),
+                false => parsed,
+            };
+            let (name, repo, contents) = name_parsed(path, parsed, true, args)?;
+            Some(Explicit {
+                path: path.clone(),
+                name,
+                repo,
+                contents,
+            })
+        })
+        .collect()
+}
+
+/// Walks the requested namespaces and, level by level, everything they include.
+/// `by_name` maps namespaces to their files and `includes` loads the files of a level,
+/// returning the namespaces each one includes. Returns the files in the order reached.
+fn include_closure<'p, T: Generator + Sync>(
+    args: &Args<'_, T>,
+    by_name: &HashMap<String, &'p Path>,
+    mut includes: impl FnMut(&[&'p Path]) -> Vec<Vec<String>>,
+) -> Result<Vec<&'p Path>, Error> {
+    let mut seen = HashSet::new();
+    let mut reached = Vec::new();
+    let mut pending: Vec<(Option<String>, String)> =
+        args.only.iter().map(|name| (None, name.clone())).collect();
+
+    while !pending.is_empty() {
+        let mut names = Vec::new();
+        let mut level = Vec::new();
+
+        for (included_by, namespace) in pending.drain(..) {
+            if !seen.insert(namespace.clone()) {
+                continue;
+            }
+            match by_name.get(&namespace) {
+                Some(path) => {
+                    level.push(*path);
+                    names.push(namespace);
+                }
+                None => {
+                    return Err(match filter_cause(args, &namespace) {
+                        Some(cause) => Error::Filtered {
+                            namespace,
+                            included_by,
+                            cause,
+                        },
+                        None => Error::Missing {
+                            namespace,
+                            included_by,
+                        },
+                    });
+                }
+            }
+        }
+
+        for (name, included) in names.into_iter().zip(includes(&level)) {
+            pending.extend(included.into_iter().map(|inc| (Some(name.clone()), inc)));
+        }
+
+        reached.extend(level);
+    }
+
+    Ok(reached)
+}
+
+/// Namespaces included by a repository, e.g "GLib-2.0".
+fn include_names(repo: &element::Repository) -> Vec<String> {
+    repo.includes
+        .iter()
+        .map(|include| format!("{}-{}", include.name, include.version))
+        .collect()
+}
+
+/// Load the requested namespaces and everything they include, the explicit inputs
+/// being looked up by their path.
+fn dependency_closure<'a, T: Generator + Sync>(
+    gir_paths: &'a [PathBuf],
+    explicit: Vec<(&'a Path, Gir<'a>)>,
+    args: &Args<'_, T>,
+) -> Result<Vec<Gir<'a>>, Error> {
+    let mut by_name: HashMap<String, &Path> = gir_paths
+        .iter()
+        .filter_map(|path| Some((path.file_stem()?.to_str()?.to_owned(), path.as_path())))
+        .collect();
+    by_name.extend(
+        explicit
+            .iter()
+            .map(|(path, gir)| (gir.name.to_owned(), *path)),
+    );
+    let mut explicit: HashMap<&Path, Gir> = explicit.into_iter().collect();
+
+    let mut girs = Vec::new();
+    include_closure(args, &by_name, |level| {
+        let mut loaded: Vec<Option<Gir>> = level
+            .par_iter()
+            .map(|path| match explicit.contains_key(path) {
+                true => None,
+                false => load(path, args),
+            })
+            .collect();
+        for (gir, path) in loaded.iter_mut().zip(level) {
+            if gir.is_none() {
+                *gir = explicit.remove(path);
+            }
+        }
+
+        let includes = loaded
+            .iter()
+            .map(|gir| gir.as_ref().map(|gir| include_names(&gir.repo)))
+            .map(Option::unwrap_or_default)
+            .collect();
+        girs.extend(loaded.into_iter().flatten());
+        includes
+    })?;
+
+    girs.sort_by_key(|gir| gir.name);
+    Ok(girs)
+}
+
+/// Loads the GIR files and runs the generator, summarizing every emitted event.
+pub fn girgen<T: Generator + Sync>(args: Args<'_, T>) -> Result<Report, Error> {
+    let report = Mutex::new(Report::default());
+    let forward = args.on_event;
+    let on_event = |event: Event| {
+        report.lock().unwrap().record(&event);
+        forward(event)
+    };
+
+    run(Args {
+        on_event: &on_event,
+        ..args
+    })?;
+
+    Ok(report.into_inner().unwrap())
+}
+
+/// Why the ignore and include patterns leave out a namespace, if they do.
+fn filter_cause<T: Generator + Sync>(args: &Args<'_, T>, name: &str) -> Option<String> {
+    if let Some(pattern) = args.ignore.iter().find(|p| p.matches(name)) {
+        Some(format!("matches ignore pattern '{pattern}'"))
+    } else if !args.include.is_empty() && !args.include.iter().any(|p| p.matches(name)) {
+        Some("not matched by any include pattern".to_owned())
+    } else {
+        None
+    }
+}
+
+/// Whether a file should be loaded, reporting why when it is not. `seen` holds the
+/// namespaces kept so far.
+fn keep<T: Generator + Sync>(
+    args: &Args<'_, T>,
+    seen: &mut HashSet<String>,
+    path: &Path,
+    name: &str,
+    event: EventSink,
+) -> bool {
+    let cause = match seen.insert(name.to_owned()) {
+        true => filter_cause(args, name),
+        false => Some("duplicate".to_owned()),
+    };
+    if let Some(cause) = &cause {
+        event(Event::Ignored {
+            file_path: path,
+            cause,
+        })
+    }
+    cause.is_none()
+}
+
+/// Files found in the dirs, filtered by the ignore and include patterns. `seen` holds
+/// the namespaces of the explicit inputs, which take precedence.
+fn gir_paths<T: Generator + Sync>(
+    args: &Args<'_, T>,
+    mut seen: HashSet<String>,
+    event: EventSink,
+) -> Vec<PathBuf> {
     let mut gir_paths = args
         .dirs
         .iter()
@@ -56,65 +404,59 @@ pub fn girgen<T: Generator + Sync>(args: Args<T>) -> Result<(), Error> {
         .flat_map(|dir| {
             dir.filter_map(Result::ok)
                 .map(|file| file.path())
-                .filter(|path| matches!(path.extension().and_then(|ext| ext.to_str()), Some("gir")))
+                .filter(|path| {
+                    matches!(
+                        path.extension().and_then(|ext| ext.to_str()),
+                        Some("gir" | "typelib")
+                    )
+                })
         })
         .collect::<Vec<_>>();
 
-    gir_paths.sort();
+    // typelibs sort last so they are only used when no GIR exists for the namespace
+    gir_paths.sort_by_key(|path| (is_typelib(path), path.clone()));
     gir_paths.dedup();
 
-    gir_paths.retain({
-        let mut uniq = std::collections::HashSet::new();
-
-        move |path| {
-            path.file_stem().is_some_and(|name| {
-                let is_new = uniq.insert(name.to_owned());
-                let ignore = args.ignore.iter().any(|ignore| **ignore == *name);
-                let keep = is_new && !ignore;
-                if !keep {
-                    (args.on_event)(Event::Ignored {
-                        file_path: path,
-                        cause: (if !is_new { "duplicate " } else { "" }),
-                    })
-                }
-                keep
-            })
-        }
+    gir_paths.retain(|path| {
+        path.file_stem()
+            .is_some_and(|name| keep(args, &mut seen, path, &name.to_string_lossy(), event))
     });
 
-    let girs: Vec<Gir> = gir_paths
-        .par_iter()
-        .filter_map(|path| {
-            let contents = match fs::read_to_string(path) {
-                Ok(ok) => ok,
-                Err(err) => {
-                    (args.on_event)(Event::ParseFailed {
-                        file_path: path,
-                        err: err.to_string().as_str(),
-                    });
-                    return None;
-                }
-            };
+    gir_paths
+}
 
-            match parse_gir::parse(&contents) {
-                Ok(repo) => {
-                    (args.on_event)(Event::Parsed { file_path: path });
-                    Some(Gir {
-                        name: path.file_stem().and_then(|f| f.to_str()).unwrap(),
-                        repo,
-                        contents,
-                    })
-                }
-                Err(err) => {
-                    (args.on_event)(Event::ParseFailed {
-                        file_path: path,
-                        err: err.to_string().as_str(),
-                    });
-                    None
-                }
-            }
+fn run<T: Generator + Sync>(args: Args<'_, T>) -> Result<(), Error> {
+    let mut seen = HashSet::new();
+    let mut explicit = load_explicit(&args);
+    explicit.retain(|e| keep(&args, &mut seen, &e.path, &e.name, args.on_event));
+
+    let gir_paths = gir_paths(&args, seen, args.on_event);
+
+    let (names, paths): (Vec<String>, Vec<PathBuf>) = explicit
+        .iter()
+        .map(|e| (e.name.clone(), e.path.clone()))
+        .unzip();
+    let explicit: Vec<(&Path, Gir)> = explicit
+        .into_iter()
+        .zip(names.iter().zip(&paths))
+        .map(|(e, (name, path))| {
+            let gir = Gir {
+                name,
+                repo: e.repo,
+                contents: e.contents,
+            };
+            (path.as_path(), gir)
         })
-        .collect::<Vec<_>>();
+        .collect();
+
+    let girs = match args.only.is_empty() {
+        true => gir_paths
+            .par_iter()
+            .filter_map(|path| load(path, &args))
+            .chain(explicit.into_par_iter().map(|(_, gir)| gir))
+            .collect::<Vec<_>>(),
+        false => dependency_closure(&gir_paths, explicit, &args)?,
+    };
 
     args.generator.generate(&girs, args.on_event)
 }
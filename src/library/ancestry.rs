@@ -0,0 +1,257 @@
+use super::{Entry, Library, Symbol};
+use crate::element::{self, Namespace};
+use std::collections::HashSet;
+use std::fmt;
+
+#[derive(Debug)]
+pub enum AncestryError {
+    /// The queried type does not exist.
+    NotFound(String),
+    /// The queried symbol is not a class or interface.
+    NotInheritable(String),
+    /// A parent, implemented interface or prerequisite could not be resolved.
+    MissingAncestor {
+        child: String,
+        ancestor: String,
+        namespace_loaded: bool,
+    },
+    /// The type hierarchy loops back onto itself, listed from the repeated type.
+    Cycle(Vec<String>),
+}
+
+impl fmt::Display for AncestryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AncestryError::NotFound(name) => write!(f, "type {name} not found"),
+            AncestryError::NotInheritable(name) => {
+                write!(f, "{name} is not a class or interface")
+            }
+            AncestryError::MissingAncestor {
+                child,
+                ancestor,
+                namespace_loaded: true,
+            } => write!(f, "{child}: ancestor {ancestor} not found"),
+            AncestryError::MissingAncestor {
+                child, ancestor, ..
+            } => {
+                let namespace = ancestor.split('.').next().unwrap_or_default();
+                write!(
+                    f,
+                    "{child}: ancestor {ancestor} not found, namespace {namespace} is not loaded"
+                )
+            }
+            AncestryError::Cycle(path) => write!(f, "inheritance cycle: {}", path.join(" -> ")),
+        }
+    }
+}
+
+impl std::error::Error for AncestryError {}
+
+/// Member of a type together with the class or interface it is declared on.
+#[derive(Debug, Clone, Copy)]
+pub struct Inherited<'a, T> {
+    pub origin: Entry<'a>,
+    pub member: &'a T,
+}
+
+type Key<'a> = (&'a str, &'a str, &'a str);
+
+fn key<'a>(entry: &Entry<'a>) -> Key<'a> {
+    (
+        &entry.namespace.name,
+        &entry.namespace.version,
+        entry.symbol.name(),
+    )
+}
+
+/// Names of the direct ancestors, the parent class first.
+fn ancestor_names<'a>(symbol: Symbol<'a>) -> Vec<&'a str> {
+    match symbol {
+        Symbol::Class(class) => class
+            .parent
+            .iter()
+            .map(String::as_str)
+            .chain(class.implements.iter().map(|i| i.name.as_str()))
+            .collect(),
+        Symbol::Interface(iface) => iface
+            .prerequisites
+            .iter()
+            .map(|p| p.name.as_str())
+            .chain(iface.implements.iter().map(|i| i.name.as_str()))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+struct Walker<'l, 'a> {
+    library: &'l Library<'a>,
+    /// Types on the path from the queried type, for cycle detection
+    stack: Vec<Entry<'a>>,
+    seen: HashSet<Key<'a>>,
+    classes: Vec<Entry<'a>>,
+    interfaces: Vec<Entry<'a>>,
+}
+
+impl<'a> Walker<'_, 'a> {
+    fn resolve(&self, child: &Entry<'a>, name: &str) -> Result<Entry<'a>, AncestryError> {
+        match self.library.resolve(child.namespace, name) {
+            Some(entry) if matches!(entry.symbol, Symbol::Class(_) | Symbol::Interface(_)) => {
+                Ok(*entry)
+            }
+            Some(entry) => Err(AncestryError::NotInheritable(entry.qualified_name())),
+            None => {
+                let namespace = name.split_once('.').map(|(ns, _)| ns);
+                let ancestor = match namespace {
+                    Some(_) => name.to_owned(),
+                    None => format!("{}.{}", child.namespace.name, name),
+                };
+                Err(AncestryError::MissingAncestor {
+                    child: child.qualified_name(),
+                    ancestor,
+                    namespace_loaded: namespace
+                        .is_none_or(|ns| self.library.versions.contains_key(ns)),
+                })
+            }
+        }
+    }
+
+    fn visit(&mut self, entry: Entry<'a>) -> Result<(), AncestryError> {
+        if let Some(pos) = self.stack.iter().position(|e| key(e) == key(&entry)) {
+            let mut path: Vec<String> = self.stack[pos..]
+                .iter()
+                .map(Entry::qualified_name)
+                .collect();
+            path.push(entry.qualified_name());
+            return Err(AncestryError::Cycle(path));
+        }
+
+        // types reached twice through different interfaces are only listed once
+        if !self.seen.insert(key(&entry)) {
+            return Ok(());
+        }
+
+        match entry.symbol {
+            Symbol::Interface(_) => self.interfaces.push(entry),
+            _ => self.classes.push(entry),
+        }
+
+        self.stack.push(entry);
+        for name in ancestor_names(entry.symbol) {
+            let ancestor = self.resolve(&entry, name)?;
+            self.visit(ancestor)?;
+        }
+        self.stack.pop();
+
+        Ok(())
+    }
+}
+
+impl<'a> Library<'a> {
+    /// The type itself followed by its parent classes, nearest first, and then every
+    /// implemented interface and prerequisite.
+    pub fn ancestry(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+    ) -> Result<Vec<Entry<'a>>, AncestryError> {
+        let entry = *self
+            .resolve(namespace, name)
+            .ok_or_else(|| AncestryError::NotFound(name.to_owned()))?;
+
+        if !matches!(entry.symbol, Symbol::Class(_) | Symbol::Interface(_)) {
+            return Err(AncestryError::NotInheritable(entry.qualified_name()));
+        }
+
+        let mut walker = Walker {
+            library: self,
+            stack: Vec::new(),
+            seen: HashSet::new(),
+            classes: Vec::new(),
+            interfaces: Vec::new(),
+        };
+        walker.visit(entry)?;
+
+        // the queried type stays first, even when it is an interface
+        let (mut result, rest) = match entry.symbol {
+            Symbol::Interface(_) => (walker.interfaces, walker.classes),
+            _ => (walker.classes, walker.interfaces),
+        };
+        result.extend(rest);
+        Ok(result)
+    }
+
+    /// Members visible on a type, including inherited ones.
+    /// Members hidden by a nearer type with the same name are skipped.
+    pub fn members<T>(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+        select: impl Fn(Symbol<'a>) -> &'a [T],
+        member_name: impl Fn(&T) -> &str,
+    ) -> Result<Vec<Inherited<'a, T>>, AncestryError> {
+        let mut names = HashSet::new();
+
+        Ok(self
+            .ancestry(namespace, name)?
+            .into_iter()
+            .flat_map(|origin| {
+                select(origin.symbol)
+                    .iter()
+                    .map(move |member| Inherited { origin, member })
+            })
+            .filter(|inherited| names.insert(member_name(inherited.member).to_owned()))
+            .collect())
+    }
+
+    pub fn properties(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+    ) -> Result<Vec<Inherited<'a, element::Property>>, AncestryError> {
+        let select = |symbol| match symbol {
+            Symbol::Class(c) => c.properties.as_slice(),
+            Symbol::Interface(i) => i.properties.as_slice(),
+            _ => &[],
+        };
+        self.members(namespace, name, select, |p| &p.name)
+    }
+
+    pub fn signals(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+    ) -> Result<Vec<Inherited<'a, element::Signal>>, AncestryError> {
+        let select = |symbol| match symbol {
+            Symbol::Class(c) => c.signals.as_slice(),
+            Symbol::Interface(i) => i.signals.as_slice(),
+            _ => &[],
+        };
+        self.members(namespace, name, select, |s| &s.name)
+    }
+
+    pub fn methods(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+    ) -> Result<Vec<Inherited<'a, element::Method>>, AncestryError> {
+        let select = |symbol| match symbol {
+            Symbol::Class(c) => c.methods.as_slice(),
+            Symbol::Interface(i) => i.methods.as_slice(),
+            _ => &[],
+        };
+        self.members(namespace, name, select, |m| &m.attrs.name)
+    }
+
+    pub fn virtual_methods(
+        &self,
+        namespace: &Namespace,
+        name: &str,
+    ) -> Result<Vec<Inherited<'a, element::VirtualMethod>>, AncestryError> {
+        let select = |symbol| match symbol {
+            Symbol::Class(c) => c.virtual_methods.as_slice(),
+            Symbol::Interface(i) => i.virtual_methods.as_slice(),
+            _ => &[],
+        };
+        self.members(namespace, name, select, |m| &m.attrs.name)
+    }
+}
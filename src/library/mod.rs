@@ -0,0 +1,400 @@
+use crate::element::{self, Include, Namespace, Repository};
+use crate::generator::Gir;
+use std::collections::{HashMap, HashSet};
+
+mod ancestry;
+pub use ancestry::*;
+
+/// Element a name resolves to.
+#[derive(Debug, Clone, Copy)]
+pub enum Symbol<'a> {
+    Class(&'a element::Class),
+    Interface(&'a element::Interface),
+    Record(&'a element::Record),
+    Enumeration(&'a element::Enumeration),
+    Bitfield(&'a element::Bitfield),
+    Union(&'a element::Union),
+    Callback(&'a element::Callback),
+    Alias(&'a element::Alias),
+    Boxed(&'a element::Boxed),
+    Constant(&'a element::Constant),
+    Function(&'a element::Function),
+    Method(&'a element::Method),
+    Constructor(&'a element::Constructor),
+    Member(&'a element::Member),
+}
+
+impl<'a> Symbol<'a> {
+    pub fn name(&self) -> &'a str {
+        match self {
+            Symbol::Class(e) => &e.name,
+            Symbol::Interface(e) => &e.name,
+            Symbol::Record(e) => &e.name,
+            Symbol::Enumeration(e) => &e.name,
+            Symbol::Bitfield(e) => &e.name,
+            Symbol::Union(e) => e.name.as_deref().unwrap_or_default(),
+            Symbol::Callback(e) => &e.name,
+            Symbol::Alias(e) => &e.name,
+            Symbol::Boxed(e) => &e.glib_name,
+            Symbol::Constant(e) => &e.name,
+            Symbol::Function(e) => &e.attrs.name,
+            Symbol::Method(e) => &e.attrs.name,
+            Symbol::Constructor(e) => &e.attrs.name,
+            Symbol::Member(e) => &e.name,
+        }
+    }
+
+    /// Whether the symbol can be used as a type, e.g in `<type name="...">`.
+    pub fn is_type(&self) -> bool {
+        !matches!(
+            self,
+            Symbol::Constant(_)
+                | Symbol::Function(_)
+                | Symbol::Method(_)
+                | Symbol::Constructor(_)
+                | Symbol::Member(_)
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Entry<'a> {
+    pub namespace: &'a Namespace,
+    /// Type the symbol is declared in, for members, methods and constructors
+    pub parent: Option<Symbol<'a>>,
+    pub symbol: Symbol<'a>,
+}
+
+impl Entry<'_> {
+    /// Name qualified with its namespace, e.g "Gtk.Widget"
+    pub fn qualified_name(&self) -> String {
+        match self.parent {
+            Some(parent) => format!(
+                "{}.{}.{}",
+                self.namespace.name,
+                parent.name(),
+                self.symbol.name()
+            ),
+            None => format!("{}.{}", self.namespace.name, self.symbol.name()),
+        }
+    }
+}
+
+type NamespaceKey<'a> = (&'a str, &'a str);
+
+/// Symbol table over every loaded repository.
+#[derive(Default)]
+pub struct Library<'a> {
+    repos: Vec<&'a Repository>,
+    entries: Vec<Entry<'a>>,
+    namespaces: HashMap<NamespaceKey<'a>, (&'a Repository, &'a Namespace)>,
+    /// Loaded versions of each namespace, in load order
+    versions: HashMap<&'a str, Vec<&'a str>>,
+    by_name: HashMap<(&'a str, &'a str, &'a str), usize>,
+    by_c_type: HashMap<&'a str, usize>,
+    by_c_identifier: HashMap<&'a str, usize>,
+    by_glib_type_name: HashMap<&'a str, usize>,
+    /// Class and interface name to its glib:is-gtype-struct-for record
+    type_structs: HashMap<(&'a str, &'a str, &'a str), &'a element::Record>,
+}
+
+impl<'a> Library<'a> {
+    pub fn new(girs: &'a [Gir]) -> Self {
+        girs.iter().map(|gir| &gir.repo).collect()
+    }
+
+    pub fn repos(&self) -> &[&'a Repository] {
+        &self.repos
+    }
+
+    pub fn namespace(&self, name: &str, version: &str) -> Option<&'a Namespace> {
+        self.namespaces.get(&(name, version)).map(|(_, ns)| *ns)
+    }
+
+    /// Loaded versions of a namespace, in load order.
+    pub fn versions(&self, name: &str) -> &[&'a str] {
+        self.versions
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Repository that declares the namespace.
+    pub fn repository(&self, name: &str, version: &str) -> Option<&'a Repository> {
+        self.namespaces.get(&(name, version)).map(|(repo, _)| *repo)
+    }
+
+    /// Resolve a qualified name like "Gtk.Widget" using the first loaded version of the namespace.
+    pub fn lookup(&self, qualified: &str) -> Option<&Entry<'a>> {
+        let (namespace, name) = qualified.split_once('.')?;
+        let version = self.versions.get(namespace)?.first()?;
+        self.get(namespace, version, name)
+    }
+
+    /// Resolve a GIR name as written inside `namespace`, unqualified names are local
+    /// and qualified ones use the version the namespace includes.
+    pub fn resolve(&self, namespace: &Namespace, name: &str) -> Option<&Entry<'a>> {
+        let Some((prefix, name)) = name.split_once('.') else {
+            return self.get(&namespace.name, &namespace.version, name);
+        };
+
+        if prefix == namespace.name {
+            return self.get(&namespace.name, &namespace.version, name);
+        }
+
+        let included = self
+            .repository(&namespace.name, &namespace.version)
+            .and_then(|repo| repo.includes.iter().find(|inc| inc.name == prefix));
+
+        match included {
+            Some(include) => self.get(&include.name, &include.version, name),
+            None => self.lookup(&format!("{prefix}.{name}")),
+        }
+    }
+
+    pub fn get(&self, namespace: &str, version: &str, name: &str) -> Option<&Entry<'a>> {
+        self.by_name
+            .get(&(namespace, version, name))
+            .map(|i| &self.entries[*i])
+    }
+
+    pub fn by_c_type(&self, c_type: &str) -> Option<&Entry<'a>> {
+        self.by_c_type.get(c_type).map(|i| &self.entries[*i])
+    }
+
+    pub fn by_c_identifier(&self, c_identifier: &str) -> Option<&Entry<'a>> {
+        self.by_c_identifier
+            .get(c_identifier)
+            .map(|i| &self.entries[*i])
+    }
+
+    pub fn by_glib_type_name(&self, type_name: &str) -> Option<&Entry<'a>> {
+        self.by_glib_type_name
+            .get(type_name)
+            .map(|i| &self.entries[*i])
+    }
+
+    /// Class struct of a class or interface, e.g "WidgetClass" for "Widget".
+    pub fn type_struct(&self, namespace: &Namespace, name: &str) -> Option<&'a element::Record> {
+        let entry = self.resolve(namespace, name)?;
+        let key = (
+            entry.namespace.name.as_str(),
+            entry.namespace.version.as_str(),
+            entry.symbol.name(),
+        );
+        self.type_structs.get(&key).copied()
+    }
+
+    /// Transitive includes of the repository, dependencies first.
+    pub fn includes(&self, repo: &Repository) -> Vec<Include> {
+        let mut visited = HashSet::new();
+        let mut result = Vec::new();
+        self.collect_includes(repo, &mut visited, &mut result);
+
+        let mut seen = HashSet::new();
+        result.retain(|inc: &Include| seen.insert((inc.name.clone(), inc.version.clone())));
+        result
+    }
+
+    fn collect_includes(
+        &self,
+        repo: &Repository,
+        visited: &mut HashSet<*const Repository>,
+        result: &mut Vec<Include>,
+    ) {
+        if !visited.insert(repo) {
+            return;
+        }
+
+        for include in &repo.includes {
+            if let Some(included) = self.repository(&include.name, &include.version) {
+                self.collect_includes(included, visited, result);
+            }
+        }
+
+        result.extend(repo.includes.iter().cloned());
+    }
+
+    fn insert(&mut self, entry: Entry<'a>) {
+        let index = self.entries.len();
+        self.entries.push(entry);
+
+        if entry.parent.is_none() {
+            let key = (
+                entry.namespace.name.as_str(),
+                entry.namespace.version.as_str(),
+                entry.symbol.name(),
+            );
+            self.by_name.entry(key).or_insert(index);
+        }
+
+        let (c_type, c_identifier, glib_type_name) = match entry.symbol {
+            Symbol::Class(e) => (e.c_type.as_deref(), None, Some(e.glib_type_name.as_str())),
+            Symbol::Interface(e) => (e.c_type.as_deref(), None, Some(e.glib_type_name.as_str())),
+            Symbol::Record(e) => (e.c_type.as_deref(), None, e.glib_type_name.as_deref()),
+            Symbol::Enumeration(e) => (Some(e.c_type.as_str()), None, e.glib_type_name.as_deref()),
+            Symbol::Bitfield(e) => (Some(e.c_type.as_str()), None, e.glib_type_name.as_deref()),
+            Symbol::Union(e) => (e.c_type.as_deref(), None, e.glib_type_name.as_deref()),
+            Symbol::Callback(e) => (e.c_type.as_deref(), None, None),
+            Symbol::Alias(e) => (Some(e.c_type.as_str()), None, None),
+            Symbol::Boxed(e) => (None, None, e.glib_type_name.as_deref()),
+            Symbol::Constant(e) => (None, e.c_identifier.as_deref(), None),
+            Symbol::Function(e) => (None, e.attrs.c_identifier.as_deref(), None),
+            Symbol::Method(e) => (None, e.attrs.c_identifier.as_deref(), None),
+            Symbol::Constructor(e) => (None, e.attrs.c_identifier.as_deref(), None),
+            Symbol::Member(e) => (None, Some(e.c_identifier.as_str()), None),
+        };
+
+        // typelibs leave some C names empty
+        if let Some(c_type) = c_type.filter(|s| !s.is_empty()) {
+            self.by_c_type.entry(c_type).or_insert(index);
+        }
+        if let Some(c_identifier) = c_identifier.filter(|s| !s.is_empty()) {
+            self.by_c_identifier.entry(c_identifier).or_insert(index);
+        }
+        if let Some(type_name) = glib_type_name.filter(|s| !s.is_empty()) {
+            self.by_glib_type_name.entry(type_name).or_insert(index);
+        }
+    }
+
+    fn insert_callables(
+        &mut self,
+        namespace: &'a Namespace,
+        parent: Symbol<'a>,
+        constructors: &'a [element::Constructor],
+        methods: &'a [element::Method],
+        functions: &'a [element::Function],
+    ) {
+        let parent = Some(parent);
+        for e in constructors {
+            let symbol = Symbol::Constructor(e);
+            self.insert(Entry {
+                namespace,
+                parent,
+                symbol,
+            });
+        }
+        for e in methods {
+            let symbol = Symbol::Method(e);
+            self.insert(Entry {
+                namespace,
+                parent,
+                symbol,
+            });
+        }
+        for e in functions {
+            let symbol = Symbol::Function(e);
+            self.insert(Entry {
+                namespace,
+                parent,
+                symbol,
+            });
+        }
+    }
+
+    fn insert_members(
+        &mut self,
+        namespace: &'a Namespace,
+        parent: Symbol<'a>,
+        members: &'a [element::Member],
+    ) {
+        for e in members {
+            let symbol = Symbol::Member(e);
+            self.insert(Entry {
+                namespace,
+                parent: Some(parent),
+                symbol,
+            });
+        }
+    }
+
+    fn insert_namespace(&mut self, namespace: &'a Namespace) {
+        let entry = |symbol| Entry {
+            namespace,
+            parent: None,
+            symbol,
+        };
+
+        for e in &namespace.classes {
+            let parent = Symbol::Class(e);
+            self.insert(entry(parent));
+            self.insert_callables(namespace, parent, &e.constructors, &e.methods, &e.functions);
+        }
+        for e in &namespace.interfaces {
+            let parent = Symbol::Interface(e);
+            self.insert(entry(parent));
+            self.insert_callables(namespace, parent, &e.constructors, &e.methods, &e.functions);
+        }
+        for e in &namespace.records {
+            let parent = Symbol::Record(e);
+            self.insert(entry(parent));
+            self.insert_callables(namespace, parent, &e.constructors, &e.methods, &e.functions);
+
+            if let Some(owner) = &e.glib_is_gtype_struct_for {
+                let key = (
+                    namespace.name.as_str(),
+                    namespace.version.as_str(),
+                    owner.as_str(),
+                );
+                self.type_structs.entry(key).or_insert(e);
+            }
+        }
+        for e in &namespace.unions {
+            let parent = Symbol::Union(e);
+            self.insert(entry(parent));
+            self.insert_callables(namespace, parent, &e.constructors, &e.methods, &e.functions);
+        }
+        for e in &namespace.enums {
+            let parent = Symbol::Enumeration(e);
+            self.insert(entry(parent));
+            self.insert_members(namespace, parent, &e.members);
+            self.insert_callables(namespace, parent, &[], &[], &e.functions);
+        }
+        for e in &namespace.bitfields {
+            let parent = Symbol::Bitfield(e);
+            self.insert(entry(parent));
+            self.insert_members(namespace, parent, &e.members);
+            self.insert_callables(namespace, parent, &[], &[], &e.functions);
+        }
+        for e in &namespace.boxeds {
+            let parent = Symbol::Boxed(e);
+            self.insert(entry(parent));
+            self.insert_callables(namespace, parent, &[], &[], &e.functions);
+        }
+        for e in &namespace.callbacks {
+            self.insert(entry(Symbol::Callback(e)));
+        }
+        for e in &namespace.aliases {
+            self.insert(entry(Symbol::Alias(e)));
+        }
+        for e in &namespace.constants {
+            self.insert(entry(Symbol::Constant(e)));
+        }
+        for e in &namespace.functions {
+            self.insert(entry(Symbol::Function(e)));
+        }
+    }
+}
+
+impl<'a> FromIterator<&'a Repository> for Library<'a> {
+    fn from_iter<T: IntoIterator<Item = &'a Repository>>(iter: T) -> Self {
+        let mut library = Library::default();
+
+        for repo in iter {
+            library.repos.push(repo);
+
+            for namespace in &repo.namespaces {
+                let key = (namespace.name.as_str(), namespace.version.as_str());
+                if library.namespaces.contains_key(&key) {
+                    continue;
+                }
+
+                library.namespaces.insert(key, (repo, namespace));
+                library.versions.entry(key.0).or_default().push(key.1);
+                library.insert_namespace(namespace);
+            }
+        }
+
+        library
+    }
+}
@@ -1,10 +1,43 @@
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use colored::Colorize;
-use girgen::generator::{debug, gjs, Error, Event};
+use girgen::config::{Config, ConfigError};
+use girgen::generator::{cache, check, debug, gir, gjs, Error, Event};
+use girgen::pattern::Pattern;
+use girgen::report::Report;
 use girgen::{default_dirs, girgen};
-use std::{ffi, path, process, sync};
+use std::io::{self, Write};
+use std::{env, ffi, path, process, sync, time};
 
 static VERBOSE: sync::OnceLock<bool> = sync::OnceLock::new();
+static LOG_FORMAT: sync::OnceLock<LogFormat> = sync::OnceLock::new();
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum LogFormat {
+    /// Colored human readable lines
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+#[derive(Clone, Copy, clap::ValueEnum)]
+enum DebugFormat {
+    /// Rust debug representation
+    Debug,
+    /// JSON array of repositories
+    Json,
+    /// One JSON repository per namespace on each line
+    Jsonl,
+}
+
+impl From<DebugFormat> for debug::Format {
+    fn from(format: DebugFormat) -> Self {
+        match format {
+            DebugFormat::Debug => debug::Format::Debug,
+            DebugFormat::Json => debug::Format::Json,
+            DebugFormat::Jsonl => debug::Format::Jsonl,
+        }
+    }
+}
 
 fn stem(path: &path::Path) -> &str {
     path.file_stem()
@@ -17,16 +50,33 @@ fn on_event(event: Event) {
         return;
     }
 
+    if let Some(LogFormat::Json) = LOG_FORMAT.get() {
+        eprintln!("{}", event.to_json());
+        return;
+    }
+
     match event {
-        Event::Parsed { file_path } => {
+        Event::Parsed {
+            repo,
+            file_path,
+            events,
+        } => {
+            let skipped = match events.len() {
+                0 => String::new(),
+                n => format!(" ({n} skipped)").yellow().to_string(),
+            };
             eprintln!(
-                "{}: {} {}",
+                "{}: {} {}{}",
                 "   parsed".green(),
-                stem(file_path),
-                file_path.display().to_string().black()
+                repo,
+                file_path.display().to_string().black(),
+                skipped,
             );
+            for event in events {
+                eprintln!("{}: {} {}", "  skipped".yellow(), repo, event);
+            }
         }
-        Event::ParseFailed { file_path, err } => {
+        Event::ParseFailed { file_path, err, .. } => {
             eprintln!(
                 "{}: could not parse {} {} {}",
                 "   failed".red(),
@@ -37,18 +87,20 @@ fn on_event(event: Event) {
         }
         Event::Ignored { file_path, cause } => {
             eprintln!(
-                "{}: {}{} {}",
+                "{}: {} {} ({})",
                 "  ignored".yellow(),
-                cause,
                 stem(file_path),
                 file_path.display().to_string().black(),
+                cause,
             );
         }
-        Event::Failed { repo, err } => match repo {
-            Some(repo) => {
+        Event::Failed {
+            repo, symbol, err, ..
+        } => match (repo, symbol) {
+            (Some(repo), None) => {
                 eprintln!("{}: failed to render {} {}", "error".red(), repo, err);
             }
-            None => {
+            _ => {
                 eprintln!("{}: {}", "error".red(), err);
             }
         },
@@ -58,9 +110,36 @@ fn on_event(event: Event) {
         Event::CacheHit { repo, out_path } => {
             eprintln!("{}: {} {}", "cache hit".green(), repo, out_path.black());
         }
+        Event::Removed { out_path } => {
+            eprintln!("{}: {}", "  removed".yellow(), out_path.black());
+        }
         Event::Warning { warning } => {
             eprintln!("{}: {}", "warning".yellow(), warning);
         }
+        Event::Rendered {
+            namespace,
+            symbols,
+            skipped,
+        } => {
+            if skipped > 0 {
+                eprintln!(
+                    "{}: {} {} failed, {} rendered",
+                    "  skipped".yellow(),
+                    namespace,
+                    skipped,
+                    symbols,
+                );
+            }
+        }
+        Event::Problem { problem } => {
+            eprintln!(
+                "{}: {} {} {}",
+                "  invalid".red(),
+                problem.repo,
+                problem.path,
+                problem.kind
+            );
+        }
     }
 }
 
@@ -71,13 +150,69 @@ struct Cli {
     #[arg(short, long, default_value_t = false)]
     silent: bool,
 
-    /// Extra directories for .gir lookup
-    #[arg(short, long, value_name = "PATHS", default_values_os_t = default_dirs())]
+    /// Format of the log written to stderr
+    #[arg(long, value_enum, default_value_t = LogFormat::Text)]
+    log_format: LogFormat,
+
+    /// Project configuration, by default the nearest girgen.toml in the current
+    /// directory or its parents. Flags take precedence over its values
+    #[arg(long, value_name = "FILE")]
+    config: Option<path::PathBuf>,
+
+    /// Extra directories for .gir lookup, in addition to the system ones
+    #[arg(short, long, value_name = "PATHS")]
     dirs: Vec<path::PathBuf>,
 
-    /// Skip rendering by name and version, e.g "Gtk-4.0"
-    #[arg(short, long, value_name = "NAMESPACE")]
-    ignore: Vec<String>,
+    /// Skip rendering by name and version, e.g "Gtk-4.0". Accepts globs like "Gtk-3.*"
+    /// or regular expressions wrapped in slashes like "/^Gst/"
+    #[arg(short, long, value_name = "PATTERN")]
+    ignore: Vec<Pattern>,
+
+    /// Only render namespaces matching any of these patterns, same syntax as --ignore
+    #[arg(long, value_name = "PATTERN")]
+    include: Vec<Pattern>,
+
+    /// GIR or typelib files to load, "-" reads from stdin. These take precedence over
+    /// files of the same namespace and version found in the dirs
+    #[arg(long = "file", value_name = "FILE")]
+    files: Vec<path::PathBuf>,
+
+    /// Only generate the given namespaces and the ones they include, e.g "Gtk-4.0"
+    #[arg(long, value_name = "NAMESPACE")]
+    only: Vec<String>,
+
+    /// Fail to parse files containing unknown GIR elements or attributes
+    #[arg(long, default_value_t = false, overrides_with = "no_strict_parse")]
+    strict_parse: bool,
+
+    /// Allow unknown GIR elements and attributes, overriding the config
+    #[arg(long, default_value_t = false, overrides_with = "strict_parse")]
+    no_strict_parse: bool,
+
+    /// Exit with an error when any file fails to parse or any symbol fails to render
+    #[arg(long, default_value_t = false, overrides_with = "no_strict")]
+    strict: bool,
+
+    /// Do not fail on parse or render failures, overriding the config
+    #[arg(
+        long,
+        default_value_t = false,
+        overrides_with = "strict",
+        conflicts_with = "max_failures"
+    )]
+    no_strict: bool,
+
+    /// Do not read or write cached declarations
+    #[arg(long, default_value_t = false)]
+    no_cache: bool,
+
+    /// Directory for cached declarations [default: $XDG_CACHE_HOME/girgen]
+    #[arg(long, value_name = "PATH", conflicts_with = "no_cache")]
+    cache_dir: Option<path::PathBuf>,
+
+    /// Exit with an error when more than N parse or render failures occur
+    #[arg(long, value_name = "N", conflicts_with = "strict")]
+    max_failures: Option<usize>,
 
     #[command(subcommand)]
     command: Language,
@@ -88,44 +223,334 @@ enum Language {
     /// Generate annotations for GJS TypeScript
     #[command(name = "gjs", alias = "typescript")]
     Gjs {
-        /// Target directory to generate to
-        #[arg(short, long, value_name = "PATH", default_value = "./.types/gi")]
-        outdir: String,
+        /// Target directory to generate to [default: ./.types/gi]
+        #[arg(short, long, value_name = "PATH")]
+        outdir: Option<String>,
 
         /// Generate non versioned import aliases
-        #[arg(short, long)]
+        #[arg(short, long, overrides_with = "no_alias")]
         alias: bool,
+
+        /// Do not generate import aliases, overriding the config
+        #[arg(long, overrides_with = "alias")]
+        no_alias: bool,
+
+        /// Declarations appended to a namespace, named after it, e.g "Gtk-4.0.d.ts"
+        #[arg(long = "override", value_name = "FILE")]
+        overrides: Vec<path::PathBuf>,
+
+        /// Remove the outdir before generating
+        #[arg(long, conflicts_with = "watch")]
+        clean: bool,
+
+        /// Keep running and generate again whenever a GIR file changes
+        #[arg(short, long)]
+        watch: bool,
+
+        /// Milliseconds between checking files for changes in watch mode
+        #[arg(long, value_name = "MS", default_value_t = 500, requires = "watch")]
+        poll_interval: u64,
+    },
+    /// Write parsed repositories back as normalized GIR files
+    Gir {
+        /// Target directory to generate to [default: ./gir-1.0]
+        #[arg(short, long, value_name = "PATH")]
+        outdir: Option<String>,
+
+        /// Remove the outdir before generating
+        #[arg(long)]
+        clean: bool,
+    },
+    /// Report semantic problems in the GIR files
+    Check,
+    /// Inspect and clean up the cache
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
     },
     /// Introspect parsed GIR data
-    Debug,
+    Debug {
+        /// Output format [default: debug, or json with --path]
+        #[arg(short, long, value_enum)]
+        format: Option<DebugFormat>,
+
+        /// Only output the given namespace, e.g "Gtk-4.0"
+        #[arg(short, long, value_name = "NAMESPACE")]
+        namespace: Option<String>,
+
+        /// Select a value by dotted path, e.g "Gtk.Widget.methods", requires a json format
+        #[arg(short, long, value_name = "PATH")]
+        path: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum CacheCommand {
+    /// Print the cache directory
+    Path,
+    /// List cache entries, least recently used first
+    List,
+    /// Print the number and size of cache entries
+    Stats,
+    /// Remove every cache entry
+    Clean,
+    /// Remove cache entries exceeding the eviction limits
+    Prune {
+        /// Total size in MiB after which the least recently used entries are removed
+        #[arg(long, value_name = "MIB")]
+        max_size: Option<u64>,
+
+        /// Days after which unused entries are removed
+        #[arg(long, value_name = "DAYS")]
+        max_age: Option<u64>,
+    },
+}
+
+fn size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1} MiB", b as f64 / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{:.1} KiB", b as f64 / 1024.0),
+        b => format!("{b} B"),
+    }
+}
+
+fn removed(out: &mut impl Write, entries: &[cache::Entry]) -> io::Result<()> {
+    let total = entries.iter().map(|entry| entry.size).sum();
+    writeln!(out, "removed {} entries ({})", entries.len(), size(total))
+}
+
+fn run_cache(command: CacheCommand, mut policy: cache::Policy) -> io::Result<()> {
+    let Some(dir) = cache::cache_dir() else {
+        eprintln!("cache is disabled");
+        return Ok(());
+    };
+
+    let mut out = io::stdout().lock();
+
+    match command {
+        CacheCommand::Path => writeln!(out, "{}", dir.display())?,
+        CacheCommand::List => {
+            for entry in cache::entries()? {
+                let name = entry.path.file_name().unwrap_or_default().to_string_lossy();
+                writeln!(out, "{:>10}  {}", size(entry.size), name)?;
+            }
+        }
+        CacheCommand::Stats => {
+            let entries = cache::entries()?;
+            let total = entries.iter().map(|entry| entry.size).sum();
+            let outdated = entries
+                .iter()
+                .filter(|entry| entry.version != cache::VERSION)
+                .count();
+            writeln!(out, "path: {}", dir.display())?;
+            writeln!(
+                out,
+                "entries: {} ({} from other versions)",
+                entries.len(),
+                outdated
+            )?;
+            writeln!(out, "size: {}", size(total))?;
+        }
+        CacheCommand::Clean => removed(&mut out, &cache::clean()?)?,
+        CacheCommand::Prune { max_size, max_age } => {
+            if let Some(mib) = max_size {
+                policy.max_size = Some(mib * 1024 * 1024);
+            }
+            if let Some(days) = max_age {
+                policy.max_age = Some(time::Duration::from_secs(days * 24 * 60 * 60));
+            }
+            removed(&mut out, &cache::prune(&policy)?)?;
+        }
+    }
+
+    Ok(())
+}
+
+fn load_config(path: Option<&path::Path>) -> Result<Config, ConfigError> {
+    let path = path.map(path::Path::to_path_buf).or_else(|| {
+        env::current_dir()
+            .ok()
+            .and_then(|dir| Config::discover(&dir))
+    });
+
+    match path {
+        Some(path) => Config::load(&path),
+        None => Ok(Config::default()),
+    }
+}
+
+/// Values given on the command line replace the ones from the config
+fn or_config<T>(cli: Vec<T>, config: Vec<T>) -> Vec<T> {
+    if cli.is_empty() { config } else { cli }
+}
+
+/// Value of a boolean option, `on` and `off` being its CLI flags.
+fn flag(on: bool, off: bool, config: bool) -> bool {
+    on || (config && !off)
+}
+
+fn outdir(cli: Option<String>, config: Option<path::PathBuf>, default: &str) -> String {
+    cli.or_else(|| config.map(|dir| dir.display().to_string()))
+        .unwrap_or_else(|| default.to_owned())
 }
 
 fn main() -> process::ExitCode {
     let cli = Cli::parse();
-    let mut dirs = [cli.dirs, default_dirs()].concat();
+
+    let config = match load_config(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{}: {}", "error".red(), err);
+            return process::ExitCode::FAILURE;
+        }
+    };
+
+    let mut dirs = [or_config(cli.dirs, config.dirs), default_dirs()].concat();
     dirs.dedup();
 
+    let ignore = or_config(cli.ignore, config.ignore);
+    let include = or_config(cli.include, config.include);
+    let files = or_config(cli.files, config.files);
+    let only = or_config(cli.only, config.only);
+
+    if cli.no_cache || (!config.cache.enabled && cli.cache_dir.is_none()) {
+        cache::set_cache_dir(None);
+    } else if let Some(dir) = cli.cache_dir.or(config.cache.dir.clone()) {
+        cache::set_cache_dir(Some(dir));
+    }
+    cache::set_policy(config.cache.policy());
+
     if cli.silent {
         VERBOSE.set(false).unwrap();
     }
+    LOG_FORMAT.set(cli.log_format).ok();
+
+    let parse_options = girgen::parse_gir::ParseOptions {
+        strict: flag(cli.strict_parse, cli.no_strict_parse, config.strict_parse),
+    };
+
+    let max_failures = cli
+        .max_failures
+        .or(cli.strict.then_some(0))
+        .or(config.max_failures.filter(|_| !cli.no_strict))
+        .or((config.strict && !cli.no_strict).then_some(0));
 
     let res = match cli.command {
-        Language::Gjs { outdir, alias } => girgen(girgen::Args {
+        Language::Cache { command } => {
+            return match run_cache(command, cache::policy()) {
+                Ok(_) => process::ExitCode::SUCCESS,
+                Err(err) => {
+                    eprintln!("{}: {}", "error".red(), err);
+                    process::ExitCode::FAILURE
+                }
+            };
+        }
+        Language::Gjs {
+            outdir: out,
+            alias,
+            no_alias,
+            overrides,
+            clean,
+            watch,
+            poll_interval,
+        } => {
+            let args = girgen::Args {
+                dirs,
+                ignore,
+                include,
+                files,
+                only,
+                parse_options,
+                on_event: &on_event,
+                generator: gjs::TypeScript {
+                    outdir: outdir(out, config.gjs.outdir, "./.types/gi"),
+                    alias: flag(alias, no_alias, config.gjs.alias),
+                    overrides: or_config(overrides, config.gjs.overrides),
+                    clean,
+                },
+            };
+
+            // unchanged namespaces are only skipped thanks to the cache
+            if watch && cache::cache_dir().is_none() {
+                Cli::command()
+                    .error(
+                        clap::error::ErrorKind::ArgumentConflict,
+                        "--watch requires the cache, it is disabled",
+                    )
+                    .exit();
+            }
+
+            match watch {
+                true => girgen::watch(args, time::Duration::from_millis(poll_interval))
+                    .map(|_| Report::default()),
+                false => girgen(args),
+            }
+        }
+        Language::Gir { outdir: out, clean } => girgen(girgen::Args {
+            dirs,
+            ignore,
+            include,
+            files,
+            only,
+            parse_options,
+            on_event: &on_event,
+            generator: gir::GirXml {
+                outdir: outdir(out, config.gir.outdir, "./gir-1.0"),
+                clean,
+            },
+        }),
+        Language::Check => girgen(girgen::Args {
             dirs,
-            ignore: cli.ignore,
-            on_event,
-            generator: gjs::TypeScript { outdir, alias },
+            ignore,
+            include,
+            files,
+            only,
+            parse_options,
+            on_event: &on_event,
+            generator: check::Check,
         }),
-        Language::Debug => girgen(girgen::Args {
+        Language::Debug {
+            format,
+            namespace,
+            path,
+        } => girgen(girgen::Args {
             dirs,
-            ignore: cli.ignore,
-            on_event,
-            generator: debug::Debug,
+            ignore,
+            include,
+            files,
+            only,
+            parse_options,
+            on_event: &on_event,
+            generator: debug::Debug {
+                format: match (format, &path) {
+                    (Some(DebugFormat::Debug), Some(_)) => Cli::command()
+                        .error(
+                            clap::error::ErrorKind::ArgumentConflict,
+                            "--path requires a json format",
+                        )
+                        .exit(),
+                    (None, Some(_)) => debug::Format::Json,
+                    (format, _) => format.map(Into::into).unwrap_or_default(),
+                },
+                namespace,
+                path,
+            },
         }),
     };
 
     match res {
-        Ok(_) => process::ExitCode::SUCCESS,
+        Ok(report) => match max_failures {
+            Some(max) if report.failures.len() > max => {
+                eprintln!(
+                    "{}: {} failures, at most {} allowed",
+                    "error".red(),
+                    report.failures.len(),
+                    max
+                );
+                process::ExitCode::FAILURE
+            }
+            _ => process::ExitCode::SUCCESS,
+        },
         Err(Error::Empty) => {
             eprintln!("nothing to generate");
             process::ExitCode::FAILURE
@@ -134,5 +559,30 @@ fn main() -> process::ExitCode {
             eprintln!("{}", err);
             process::ExitCode::FAILURE
         }
+        Err(Error::Missing {
+            namespace,
+            included_by,
+        }) => {
+            match included_by {
+                Some(repo) => eprintln!("{repo} includes {namespace}, which was not found"),
+                None => eprintln!("{namespace} was not found"),
+            }
+            process::ExitCode::FAILURE
+        }
+        Err(Error::Filtered {
+            namespace,
+            included_by,
+            cause,
+        }) => {
+            match included_by {
+                Some(repo) => eprintln!("{repo} includes {namespace}, which {cause}"),
+                None => eprintln!("{namespace} {cause}"),
+            }
+            process::ExitCode::FAILURE
+        }
+        Err(Error::Invalid(n)) => {
+            eprintln!("found {n} problems");
+            process::ExitCode::FAILURE
+        }
     }
 }
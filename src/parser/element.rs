@@ -22,6 +22,7 @@ mod property;
 mod record;
 mod repository;
 mod signal;
+mod transfer;
 mod r#type;
 mod union;
 
@@ -50,15 +51,70 @@ pub use property::*;
 pub use record::*;
 pub use repository::*;
 pub use signal::*;
+pub use transfer::*;
 pub use r#type::*;
 pub use union::*;
 
-pub struct Attrs(pub std::collections::HashMap<String, String>);
+/// Serialize attribute enums as their GIR string value.
+macro_rules! serde_as_str {
+    ($($name:ident),*) => {$(
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let value = String::deserialize(deserializer)?;
+                value.parse().map_err(serde::de::Error::custom)
+            }
+        }
+    )*};
+}
+
+serde_as_str!(
+    Direction,
+    Scope,
+    Stability,
+    SignalWhen,
+    DocFormatKind,
+    TransferOwnership
+);
+
+pub struct Attrs {
+    pub attrs: std::collections::HashMap<String, String>,
+    used: std::cell::RefCell<std::collections::HashSet<String>>,
+}
 
 impl Attrs {
+    pub fn new(attrs: std::collections::HashMap<String, String>) -> Self {
+        Self {
+            attrs,
+            used: Default::default(),
+        }
+    }
+
+    /// Attributes that were never looked up by the element, excluding namespace declarations.
+    pub fn unused(&self) -> Vec<&str> {
+        let used = self.used.borrow();
+        let mut unused: Vec<&str> = self
+            .attrs
+            .keys()
+            .filter(|key| *key != "xmlns" && !key.starts_with("xmlns:"))
+            .filter(|key| !used.contains(*key))
+            .map(String::as_str)
+            .collect();
+
+        unused.sort();
+        unused
+    }
+
     fn get_string(&self, key: &str) -> Result<String, ParseError> {
+        self.used.borrow_mut().insert(key.to_owned());
+
         let res = self
-            .0
+            .attrs
             .get(key)
             .ok_or(ParseError::MissingAttribute(String::from(key)))?;
 
@@ -74,6 +130,13 @@ impl Attrs {
         }
     }
 
+    fn get_parsed<T: std::str::FromStr<Err = ParseError>>(
+        &self,
+        key: &str,
+    ) -> Result<T, ParseError> {
+        self.get_string(key)?.parse()
+    }
+
     fn get_int(&self, key: &str) -> Result<i32, ParseError> {
         let value = self.get_string(key)?;
         value
@@ -242,7 +305,7 @@ impl AnyElement {
             b"docsection" => Self::DocSection(DocSection::new(attrs)?),
             tag => {
                 return Err(ParseError::InvalidGirElement(
-                    str::from_utf8(tag).unwrap().to_owned(),
+                    String::from_utf8_lossy(tag).into_owned(),
                 ));
             }
         };
@@ -1,6 +1,6 @@
-use super::{AnyElement, AnyType, Attrs, DocElement, InfoAttrs, ParseError};
+use super::{AnyElement, AnyType, Attrs, DocElement, InfoAttrs, ParseError, TransferOwnership};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CallableAttrs {
     pub info: InfoAttrs,
     pub name: String,
@@ -14,28 +14,97 @@ pub struct CallableAttrs {
     pub glib_finish_func: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Direction {
+    In,
+    Out,
+    InOut,
+    Unknown(String),
+}
+
+impl std::str::FromStr for Direction {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "in" => Self::In,
+            "out" => Self::Out,
+            "inout" => Self::InOut,
+            s => Self::Unknown(s.to_owned()),
+        })
+    }
+}
+
+impl Direction {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::In => "in",
+            Self::Out => "out",
+            Self::InOut => "inout",
+            Self::Unknown(s) => s,
+        }
+    }
+}
+
+/// Lifetime of a callback parameter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Scope {
+    Call,
+    Async,
+    Notified,
+    Forever,
+    Unknown(String),
+}
+
+impl std::str::FromStr for Scope {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "call" => Self::Call,
+            "async" => Self::Async,
+            "notified" => Self::Notified,
+            "forever" => Self::Forever,
+            s => Self::Unknown(s.to_owned()),
+        })
+    }
+}
+
+impl Scope {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Call => "call",
+            Self::Async => "async",
+            Self::Notified => "notified",
+            Self::Forever => "forever",
+            Self::Unknown(s) => s,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct VarArgs;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Parameters {
     pub instance_parameter: Option<InstanceParameter>,
     pub parameters: Vec<Parameter>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct InstanceParameter {
     pub name: String,
     pub nullable: Option<bool>,
     pub allow_none: Option<bool>, // depreacted, replaced by nullable and optional
-    pub direction: Option<String>, // "out" | "in" | "inout"
+    pub direction: Option<Direction>,
     pub caller_allocates: Option<bool>,
+    pub transfer_ownership: Option<TransferOwnership>,
 
     pub r#type: Option<AnyType>,
     pub doc_elements: Vec<DocElement>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Parameter {
     pub name: Option<String>,
     pub nullable: Option<bool>,
@@ -43,11 +112,12 @@ pub struct Parameter {
     pub introspectable: Option<bool>,
     pub closure: Option<i32>,
     pub destroy: Option<i32>,
-    pub scope: Option<String>, // "notified" | "async" | "call" | "forever"
-    pub direction: Option<String>, // "out" | "in" | "inout"
+    pub scope: Option<Scope>,
+    pub direction: Option<Direction>,
     pub caller_allocates: Option<bool>,
     pub optional: Option<bool>,
     pub skip: Option<bool>,
+    pub transfer_ownership: Option<TransferOwnership>,
 
     pub doc_elements: Vec<DocElement>,
     pub varargs: Option<VarArgs>,
@@ -55,15 +125,16 @@ pub struct Parameter {
     pub annotations: Vec<super::Attribute>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct ReturnValue {
     pub introspectable: Option<bool>,
     pub nullable: Option<bool>,
     pub closure: Option<i32>,
-    pub scope: Option<String>, // "notified" | "async" | "call" | "forever"
+    pub scope: Option<Scope>,
     pub destroy: Option<i32>,
     pub skip: Option<bool>,
     pub allow_none: Option<bool>, // depreacted, replaced by nullable and optional
+    pub transfer_ownership: Option<TransferOwnership>,
 
     pub doc_elements: Vec<DocElement>,
     pub annotations: Vec<super::Attribute>,
@@ -127,8 +198,9 @@ impl super::Element for InstanceParameter {
             name: attrs.get_string("name")?,
             nullable: attrs.get_boolean("nullable").ok(),
             allow_none: attrs.get_boolean("allow-none").ok(),
-            direction: attrs.get_string("direction").ok(),
+            direction: attrs.get_parsed("direction").ok(),
             caller_allocates: attrs.get_boolean("caller-allocates").ok(),
+            transfer_ownership: attrs.get_parsed("transfer-ownership").ok(),
             r#type: None,
             doc_elements: Vec::new(),
         })
@@ -164,11 +236,12 @@ impl super::Element for Parameter {
             introspectable: attrs.get_boolean("introspectable").ok(),
             closure: attrs.get_int("closure").ok(),
             destroy: attrs.get_int("destroy").ok(),
-            scope: attrs.get_string("scope").ok(),
-            direction: attrs.get_string("direction").ok(),
+            scope: attrs.get_parsed("scope").ok(),
+            direction: attrs.get_parsed("direction").ok(),
             caller_allocates: attrs.get_boolean("caller-allocates").ok(),
             optional: attrs.get_boolean("optional").ok(),
             skip: attrs.get_boolean("skip").ok(),
+            transfer_ownership: attrs.get_parsed("transfer-ownership").ok(),
             doc_elements: Vec::new(),
             varargs: None,
             r#type: None,
@@ -217,10 +290,11 @@ impl super::Element for ReturnValue {
             introspectable: attrs.get_boolean("introspectable").ok(),
             nullable: attrs.get_boolean("nullable").ok(),
             closure: attrs.get_int("closure").ok(),
-            scope: attrs.get_string("scope").ok(),
+            scope: attrs.get_parsed("scope").ok(),
             destroy: attrs.get_int("destroy").ok(),
             skip: attrs.get_boolean("skip").ok(),
             allow_none: attrs.get_boolean("allow-none").ok(),
+            transfer_ownership: attrs.get_parsed("transfer-ownership").ok(),
             doc_elements: Vec::new(),
             annotations: Vec::new(),
             r#type: None,
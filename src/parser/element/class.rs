@@ -1,6 +1,6 @@
 use super::{AnyElement, Attrs, InfoAttrs, InfoElement, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Class {
     pub info: InfoAttrs,
     pub name: String,
@@ -35,7 +35,7 @@ pub struct Class {
     pub callbacks: Vec<super::Callback>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Implements {
     pub name: String,
 }
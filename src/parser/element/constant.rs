@@ -1,6 +1,6 @@
 use super::{AnyElement, AnyType, Attrs, InfoAttrs, InfoElement, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Constant {
     pub info: InfoAttrs,
     pub name: String,
@@ -1,6 +1,6 @@
 use super::{AnyElement, Attrs, CallableAttrs, InfoElement, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Constructor {
     pub attrs: CallableAttrs,
     pub info_elements: Vec<InfoElement>,
@@ -1,12 +1,46 @@
 use super::{AnyElement, Attrs, DocElement, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DocFormat {
-    // Valid values are: gi-docgen, gtk-doc-docbook, gtk-doc-markdown, hotdoc, unknown.
-    pub name: String,
+    pub name: DocFormatKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DocFormatKind {
+    GiDocgen,
+    GtkDocDocbook,
+    GtkDocMarkdown,
+    Hotdoc,
+    Unknown(String),
 }
 
-#[derive(Debug, Clone)]
+impl std::str::FromStr for DocFormatKind {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "gi-docgen" => Self::GiDocgen,
+            "gtk-doc-docbook" => Self::GtkDocDocbook,
+            "gtk-doc-markdown" => Self::GtkDocMarkdown,
+            "hotdoc" => Self::Hotdoc,
+            s => Self::Unknown(s.to_owned()),
+        })
+    }
+}
+
+impl DocFormatKind {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::GiDocgen => "gi-docgen",
+            Self::GtkDocDocbook => "gtk-doc-docbook",
+            Self::GtkDocMarkdown => "gtk-doc-markdown",
+            Self::Hotdoc => "hotdoc",
+            Self::Unknown(s) => s,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DocSection {
     pub name: String,
     pub elements: Vec<DocElement>,
@@ -17,7 +51,7 @@ impl super::Element for DocFormat {
 
     fn new(attrs: &Attrs) -> Result<Self, ParseError> {
         Ok(Self {
-            name: attrs.get_string("name")?,
+            name: attrs.get_parsed("name")?,
         })
     }
 }
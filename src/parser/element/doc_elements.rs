@@ -1,20 +1,20 @@
 use super::{AnyElement, Attrs, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DocVersion {
     pub xml_space: Option<String>,
     pub xml_whitespace: Option<String>,
     pub text: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DocStability {
     pub xml_space: Option<String>,
     pub xml_whitespace: Option<String>,
     pub text: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Doc {
     pub xml_space: Option<String>,
     pub xml_whitespace: Option<String>,
@@ -24,21 +24,21 @@ pub struct Doc {
     pub text: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct DocDeprecated {
     pub xml_space: Option<String>,
     pub xml_whitespace: Option<String>,
     pub text: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct SourcePosition {
     pub filename: String,
     pub line: String,
     pub column: Option<String>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum DocElement {
     DocVersion(DocVersion),
     DocStability(DocStability),
@@ -104,7 +104,7 @@ impl super::Element for Doc {
             xml_whitespace: attrs.get_string("xml:whitespace").ok(),
             filename: attrs.get_string("filename").ok(),
             line: attrs.get_string("line").ok(),
-            column: attrs.get_string("line").ok(),
+            column: attrs.get_string("column").ok(),
             text: String::from(""),
         })
     }
@@ -1,6 +1,6 @@
-use super::{AnyElement, AnyType, Attrs, InfoAttrs, InfoElement, ParseError};
+use super::{AnyElement, AnyType, Attrs, InfoAttrs, InfoElement, ParseError, TransferOwnership};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Field {
     pub info: InfoAttrs,
     pub name: String,
@@ -8,6 +8,7 @@ pub struct Field {
     pub readable: Option<bool>,
     pub private: Option<bool>,
     pub bits: Option<i32>,
+    pub transfer_ownership: Option<TransferOwnership>,
 
     pub info_elements: Vec<InfoElement>,
     pub callback: Option<super::Callback>,
@@ -25,6 +26,7 @@ impl super::Element for Field {
             readable: attrs.get_boolean("readable").ok(),
             private: attrs.get_boolean("private").ok(),
             bits: attrs.get_int("bits").ok(),
+            transfer_ownership: attrs.get_parsed("transfer-ownership").ok(),
             info_elements: Vec::new(),
             callback: None,
             r#type: None,
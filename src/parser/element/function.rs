@@ -1,6 +1,6 @@
 use super::{AnyElement, Attrs, CallableAttrs, DocElement, InfoElement, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Function {
     pub attrs: CallableAttrs,
     pub info_elements: Vec<InfoElement>,
@@ -8,7 +8,7 @@ pub struct Function {
     pub return_value: Option<super::ReturnValue>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FunctionInline {
     pub attrs: CallableAttrs,
     pub parameters: Option<super::Parameters>,
@@ -16,7 +16,7 @@ pub struct FunctionInline {
     pub doc_elements: Vec<DocElement>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct FunctionMacro {
     pub attrs: CallableAttrs,
     pub info_elements: Vec<InfoElement>,
@@ -1,15 +1,47 @@
-use super::{AnyElement, Attribute, Attrs, DocElement};
+use super::{AnyElement, Attribute, Attrs, DocElement, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct InfoAttrs {
     pub introspectable: Option<bool>,
     pub deprecated: Option<bool>,
     pub deprecated_version: Option<String>,
     pub version: Option<String>,
-    pub stability: Option<String>, // "Stable" | "Unstable" | "Private"
+    pub stability: Option<Stability>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stability {
+    Stable,
+    Unstable,
+    Private,
+    Unknown(String),
+}
+
+impl std::str::FromStr for Stability {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "Stable" => Self::Stable,
+            "Unstable" => Self::Unstable,
+            "Private" => Self::Private,
+            s => Self::Unknown(s.to_owned()),
+        })
+    }
+}
+
+impl Stability {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Stable => "Stable",
+            Self::Unstable => "Unstable",
+            Self::Private => "Private",
+            Self::Unknown(s) => s,
+        }
+    }
+}
+
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum InfoElement {
     Annotation(Attribute),
     DocElement(DocElement),
@@ -33,7 +65,7 @@ impl InfoAttrs {
             deprecated: attrs.get_boolean("deprecated").ok(),
             deprecated_version: attrs.get_string("deprecated-version").ok(),
             version: attrs.get_string("version").ok(),
-            stability: attrs.get_string("stability").ok(),
+            stability: attrs.get_parsed("stability").ok(),
         }
     }
 }
@@ -1,10 +1,13 @@
 use super::{AnyElement, Attrs, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Namespace {
     pub name: String,
     pub version: String,
+    pub c_identifier_prefixes: Option<String>,
     pub c_symbol_prefixes: Option<String>,
+    pub c_prefix: Option<String>, // deprecated, replaced by c:identifier-prefixes
+    pub shared_library: Option<String>,
     pub aliases: Vec<super::Alias>,
     pub classes: Vec<super::Class>,
     pub interfaces: Vec<super::Interface>,
@@ -29,7 +32,10 @@ impl super::Element for Namespace {
         Ok(Self {
             name: attrs.get_string("name")?,
             version: attrs.get_string("version")?,
+            c_identifier_prefixes: attrs.get_string("c:identifier-prefixes").ok(),
             c_symbol_prefixes: attrs.get_string("c:symbol-prefixes").ok(),
+            c_prefix: attrs.get_string("c:prefix").ok(),
+            shared_library: attrs.get_string("shared-library").ok(),
             aliases: Vec::new(),
             classes: Vec::new(),
             interfaces: Vec::new(),
@@ -1,6 +1,6 @@
 use super::{Attrs, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Package {
     pub name: String,
 }
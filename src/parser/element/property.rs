@@ -1,6 +1,6 @@
-use super::{AnyType, Attrs, InfoAttrs, InfoElement, ParseError};
+use super::{AnyType, Attrs, InfoAttrs, InfoElement, ParseError, TransferOwnership};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Property {
     pub info: InfoAttrs,
     pub name: String,
@@ -11,6 +11,7 @@ pub struct Property {
     pub setter: Option<String>,
     pub getter: Option<String>,
     pub default_value: Option<String>,
+    pub transfer_ownership: Option<TransferOwnership>,
 
     pub r#type: Option<AnyType>,
     pub info_elements: Vec<InfoElement>,
@@ -30,6 +31,7 @@ impl super::Element for Property {
             setter: attrs.get_string("setter").ok(),
             getter: attrs.get_string("getter").ok(),
             default_value: attrs.get_string("default-value").ok(),
+            transfer_ownership: attrs.get_parsed("transfer-ownership").ok(),
             r#type: None,
             info_elements: Vec::new(),
         })
@@ -1,6 +1,6 @@
 use super::{AnyElement, Attrs, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Repository {
     pub version: Option<String>,
     pub c_identifier_prefixes: Option<String>,
@@ -13,13 +13,13 @@ pub struct Repository {
     pub doc_formats: Vec<super::DocFormat>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Include {
     pub name: String,
     pub version: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CInclude {
     pub name: String,
 }
@@ -77,6 +77,8 @@ impl super::Element for Repository {
 }
 
 impl Repository {
+    /// Transitive includes of the repository found in `repos`, dependencies first.
+    /// Prefer [`crate::library::Library::includes`], which also handles include cycles.
     pub fn find_includes(&self, repos: &[&Repository]) -> Vec<Include> {
         let mut result = self
             .includes
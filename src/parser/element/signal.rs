@@ -1,11 +1,11 @@
 use super::{AnyElement, Attrs, InfoAttrs, InfoElement, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Signal {
     pub info: InfoAttrs,
     pub name: String,
     pub detailed: Option<bool>,
-    pub when: Option<String>,
+    pub when: Option<SignalWhen>,
     pub action: Option<bool>,
     pub no_hooks: Option<bool>,
     pub no_recurse: Option<bool>,
@@ -16,6 +16,39 @@ pub struct Signal {
     pub return_value: Option<super::ReturnValue>,
 }
 
+/// Emission stage in which the class closure is invoked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SignalWhen {
+    First,
+    Last,
+    Cleanup,
+    Unknown(String),
+}
+
+impl std::str::FromStr for SignalWhen {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "first" => Self::First,
+            "last" => Self::Last,
+            "cleanup" => Self::Cleanup,
+            s => Self::Unknown(s.to_owned()),
+        })
+    }
+}
+
+impl SignalWhen {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::First => "first",
+            Self::Last => "last",
+            Self::Cleanup => "cleanup",
+            Self::Unknown(s) => s,
+        }
+    }
+}
+
 impl super::Element for Signal {
     const KIND: &'static str = "signal";
 
@@ -24,10 +57,10 @@ impl super::Element for Signal {
             info: InfoAttrs::new(attrs),
             name: attrs.get_string("name")?,
             detailed: attrs.get_boolean("detailed").ok(),
-            when: attrs.get_string("when").ok(),
+            when: attrs.get_parsed("when").ok(),
             action: attrs.get_boolean("action").ok(),
-            no_hooks: attrs.get_boolean("no_hooks").ok(),
-            no_recurse: attrs.get_boolean("no_recurse").ok(),
+            no_hooks: attrs.get_boolean("no-hooks").ok(),
+            no_recurse: attrs.get_boolean("no-recurse").ok(),
             emitter: attrs.get_string("emitter").ok(),
             info_elements: Vec::new(),
             parameters: None,
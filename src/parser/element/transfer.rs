@@ -0,0 +1,36 @@
+use super::ParseError;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TransferOwnership {
+    None,
+    Container,
+    Full,
+    Floating,
+    Unknown(String),
+}
+
+impl std::str::FromStr for TransferOwnership {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "none" => Self::None,
+            "container" => Self::Container,
+            "full" => Self::Full,
+            "floating" => Self::Floating,
+            s => Self::Unknown(s.to_owned()),
+        })
+    }
+}
+
+impl TransferOwnership {
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::None => "none",
+            Self::Container => "container",
+            Self::Full => "full",
+            Self::Floating => "floating",
+            Self::Unknown(s) => s,
+        }
+    }
+}
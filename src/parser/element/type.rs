@@ -1,6 +1,6 @@
 use super::{AnyElement, Attrs, DocElement, ParseError};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Type {
     pub name: Option<String>,
     pub c_type: Option<String>,
@@ -10,7 +10,7 @@ pub struct Type {
     pub elements: Vec<AnyType>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct Array {
     pub name: Option<String>,
     pub c_type: Option<String>,
@@ -22,7 +22,7 @@ pub struct Array {
     pub elements: Vec<AnyType>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub enum AnyType {
     Type(Type),
     Array(Array),
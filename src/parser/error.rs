@@ -1,15 +1,129 @@
 use std::fmt;
 
+/// Position of an element in the source GIR file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    /// Element path stack, e.g `repository>namespace>class[Widget]>method`
+    pub path: String,
+}
+
+impl Location {
+    pub fn new(contents: &str, position: usize, path: String) -> Self {
+        let before = &contents[..position.min(contents.len())];
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            path,
+        }
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)?;
+        if !self.path.is_empty() {
+            write!(f, " ({})", self.path)?;
+        }
+        Ok(())
+    }
+}
+
+/// Non fatal problems encountered while parsing, where part of the GIR was skipped.
+#[derive(Debug, Clone)]
+pub enum ParseEvent {
+    /// Element could not be parsed and was dropped together with its children.
+    DroppedElement {
+        tag: String,
+        reason: String,
+        location: Location,
+    },
+    /// Element is not accepted as a child of its parent and was dropped.
+    UnexpectedElement {
+        parent: &'static str,
+        child: &'static str,
+        location: Location,
+    },
+    /// Attribute is not handled by the element and was ignored.
+    UnknownAttribute {
+        tag: String,
+        attribute: String,
+        location: Location,
+    },
+}
+
+impl ParseEvent {
+    pub fn location(&self) -> &Location {
+        match self {
+            ParseEvent::DroppedElement { location, .. } => location,
+            ParseEvent::UnexpectedElement { location, .. } => location,
+            ParseEvent::UnknownAttribute { location, .. } => location,
+        }
+    }
+}
+
+impl fmt::Display for ParseEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseEvent::DroppedElement {
+                tag,
+                reason,
+                location,
+            } => write!(f, "dropped element {tag}: {reason} at {location}"),
+            ParseEvent::UnexpectedElement {
+                parent,
+                child,
+                location,
+            } => write!(f, "unexpected element {parent}>{child} at {location}"),
+            ParseEvent::UnknownAttribute {
+                tag,
+                attribute,
+                location,
+            } => write!(f, "unknown attribute {tag}@{attribute} at {location}"),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum ParseError {
     InvalidGirElement(String),
     MalformedGir(&'static str),
+    MalformedTypelib(&'static str),
     UnexpectedElement(&'static str, &'static str),
     MissingAttribute(String),
+    UnknownAttribute(String, String),
     XmlError(quick_xml::Error),
     EncodeError(quick_xml::encoding::EncodingError),
     AttrError(quick_xml::events::attributes::AttrError),
     Utf8Error(std::string::FromUtf8Error),
+    At(Location, Box<ParseError>),
+}
+
+impl ParseError {
+    pub fn at(self, location: Location) -> Self {
+        match self {
+            ParseError::At(_, _) => self,
+            err => ParseError::At(location, Box::new(err)),
+        }
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        match self {
+            ParseError::At(location, _) => Some(location),
+            _ => None,
+        }
+    }
+
+    /// The underlying error without its location.
+    pub fn kind(&self) -> &ParseError {
+        match self {
+            ParseError::At(_, err) => err.kind(),
+            err => err,
+        }
+    }
 }
 
 impl From<std::string::FromUtf8Error> for ParseError {
@@ -35,14 +149,19 @@ impl fmt::Display for ParseError {
         match self {
             ParseError::InvalidGirElement(tag) => write!(f, "unknown GIR element: {tag}"),
             ParseError::MalformedGir(msg) => write!(f, "malformed GIR: {msg}"),
+            ParseError::MalformedTypelib(msg) => write!(f, "malformed typelib: {msg}"),
             ParseError::UnexpectedElement(parent, child) => {
                 write!(f, "unexpected element: {parent}>{child}")
             }
             ParseError::MissingAttribute(attr) => write!(f, "missing attribute: {attr}"),
+            ParseError::UnknownAttribute(tag, attr) => {
+                write!(f, "unknown attribute: {tag}@{attr}")
+            }
             ParseError::XmlError(err) => write!(f, "XML error: {err}"),
             ParseError::EncodeError(err) => write!(f, "encoding error: {err}"),
             ParseError::Utf8Error(err) => write!(f, "utf-8 error: {err}"),
             ParseError::AttrError(err) => write!(f, "decoding error: {err}"),
+            ParseError::At(location, err) => write!(f, "{err} at {location}"),
         }
     }
 }
@@ -53,6 +172,7 @@ impl std::error::Error for ParseError {
             ParseError::XmlError(e) => Some(e),
             ParseError::EncodeError(e) => Some(e),
             ParseError::Utf8Error(e) => Some(e),
+            ParseError::At(_, e) => e.source(),
             _ => None,
         }
     }
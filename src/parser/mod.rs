@@ -1,3 +1,5 @@
 pub mod element;
 pub mod error;
 pub mod parse_gir;
+pub mod parse_typelib;
+mod write_gir;
@@ -1,5 +1,5 @@
 use super::element::{AnyElement, Attrs, Element, Repository};
-use super::error::ParseError;
+use super::error::{Location, ParseError, ParseEvent};
 use quick_xml::XmlVersion;
 use quick_xml::escape::resolve_xml_entity;
 use quick_xml::events::{BytesStart, Event};
@@ -18,93 +18,184 @@ fn attributes(e: &BytesStart) -> Result<Attrs, ParseError> {
         }
     }
 
-    Ok(Attrs(attrs))
+    Ok(Attrs::new(attrs))
 }
 
-impl AnyElement {
-    // TODO: impl ParseEvent and log invalid elements
-    fn push(&mut self, e: &BytesStart) -> Result<Self, ParseError> {
+fn segment(e: &BytesStart) -> String {
+    let tag = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+    match e.try_get_attribute("name") {
+        Ok(Some(name)) => format!("{}[{}]", tag, String::from_utf8_lossy(&name.value)),
+        _ => tag,
+    }
+}
+
+/// Options for [`parse_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    /// Fail on elements and attributes that would otherwise be skipped.
+    pub strict: bool,
+}
+
+struct Parser<'a> {
+    options: &'a ParseOptions,
+    contents: &'a str,
+    position: u64,
+    repo: Option<Repository>,
+    stack: Vec<AnyElement>,
+    path: Vec<String>,
+    events: Vec<ParseEvent>,
+}
+
+impl Parser<'_> {
+    fn location(&self) -> Location {
+        let position = usize::try_from(self.position).unwrap_or(usize::MAX);
+        Location::new(self.contents, position, self.path.join(">"))
+    }
+
+    fn report(&mut self, event: ParseEvent, err: ParseError) -> Result<(), ParseError> {
+        if self.options.strict {
+            return Err(err);
+        }
+        self.events.push(event);
+        Ok(())
+    }
+
+    fn push(&mut self, e: &BytesStart) -> Result<(), ParseError> {
+        let parent = self
+            .stack
+            .last()
+            .ok_or(ParseError::MalformedGir("failed to push: stack is empty"))?;
+
+        // children of dropped elements are dropped silently
+        if matches!(parent, AnyElement::Invalid) {
+            self.stack.push(AnyElement::Invalid);
+            return Ok(());
+        }
+
+        let tag = String::from_utf8_lossy(e.name().as_ref()).into_owned();
+
         let attrs = match attributes(e) {
             Ok(ok) => ok,
-            Err(_) => return Ok(Self::Invalid),
+            Err(err) => {
+                let event = ParseEvent::DroppedElement {
+                    tag,
+                    reason: err.to_string(),
+                    location: self.location(),
+                };
+                self.report(event, err)?;
+                self.stack.push(AnyElement::Invalid);
+                return Ok(());
+            }
+        };
+
+        let ele = match AnyElement::new(e.name().as_ref(), &attrs) {
+            Ok(ok) => ok,
+            Err(err @ (ParseError::MissingAttribute(_) | ParseError::InvalidGirElement(_))) => {
+                let event = ParseEvent::DroppedElement {
+                    tag,
+                    reason: err.to_string(),
+                    location: self.location(),
+                };
+                self.report(event, err)?;
+                self.stack.push(AnyElement::Invalid);
+                return Ok(());
+            }
+            Err(err) => return Err(err),
         };
-        match AnyElement::new(e.name().as_ref(), &attrs) {
-            Ok(ok) => Ok(ok),
-            Err(err) => match err {
-                ParseError::MissingAttribute(_) => Ok(Self::Invalid),
-                ParseError::InvalidGirElement(_) => Ok(Self::Invalid),
-                err => Err(err),
-            },
+
+        for attribute in attrs.unused() {
+            let event = ParseEvent::UnknownAttribute {
+                tag: tag.clone(),
+                attribute: attribute.to_owned(),
+                location: self.location(),
+            };
+            let err = ParseError::UnknownAttribute(tag.clone(), attribute.to_owned());
+            self.report(event, err)?;
         }
+
+        self.stack.push(ele);
+        Ok(())
     }
-}
 
-pub fn parse(gir_contents: &str) -> Result<Repository, ParseError> {
-    let mut reader = quick_xml::Reader::from_str(gir_contents);
+    fn pop(&mut self) -> Result<(), ParseError> {
+        let top = self
+            .stack
+            .pop()
+            .ok_or(ParseError::MalformedGir("failed to end: stack pop"))?;
 
-    let mut repo: Option<Repository> = None;
-    let mut stack: Vec<AnyElement> = Vec::new();
+        let second = self
+            .stack
+            .last_mut()
+            .ok_or(ParseError::MalformedGir("failed to end: stack is empty"))?;
 
-    loop {
-        match reader.read_event() {
-            Ok(Event::Start(e)) => match e.name().as_ref() {
-                b"repository" => {
-                    let attrs = attributes(&e)?;
-                    stack.push(AnyElement::Repository(Repository::new(&attrs)?));
+        if !matches!(second, AnyElement::Invalid) && !matches!(top, AnyElement::Invalid) {
+            match second.end(top) {
+                Err(err @ ParseError::UnexpectedElement(parent, child)) => {
+                    let event = ParseEvent::UnexpectedElement {
+                        parent,
+                        child,
+                        location: self.location(),
+                    };
+                    self.report(event, err)?;
                 }
-                _ => {
-                    let ele = stack
-                        .last_mut()
-                        .ok_or(ParseError::MalformedGir("failed to push: stack is empty"))?
-                        .push(&e)?;
+                Err(err) => return Err(err),
+                Ok(_) => (),
+            }
+        }
 
-                    stack.push(ele);
-                }
-            },
-            Ok(Event::End(e)) => match e.name().as_ref() {
-                b"repository" => {
-                    if let AnyElement::Repository(r) = stack.pop().ok_or(
-                        ParseError::MalformedGir("root element expected to be repository"),
-                    )? {
-                        repo = Some(r);
+        Ok(())
+    }
+
+    fn handle(&mut self, event: Event) -> Result<(), ParseError> {
+        match event {
+            Event::Start(e) => {
+                self.path.push(segment(&e));
+                match e.name().as_ref() {
+                    b"repository" => {
+                        let attrs = attributes(&e)?;
+                        self.stack
+                            .push(AnyElement::Repository(Repository::new(&attrs)?));
                     }
+                    _ => self.push(&e)?,
                 }
-                _ => {
-                    let top = stack
-                        .pop()
-                        .ok_or(ParseError::MalformedGir("failed to end: stack pop"))?;
-
-                    let second = stack
-                        .last_mut()
-                        .ok_or(ParseError::MalformedGir("failed to end: stack is empty"))?;
-
-                    if !matches!(second, AnyElement::Invalid) && !matches!(top, AnyElement::Invalid)
-                    {
-                        match second.end(top) {
-                            // TODO: impl ParseEvent and log invalid elements
-                            Err(ParseError::UnexpectedElement(_, _)) => (),
-                            Err(err) => return Err(err),
-                            Ok(_) => (),
+            }
+            Event::End(e) => {
+                match e.name().as_ref() {
+                    b"repository" => {
+                        if let AnyElement::Repository(r) = self.stack.pop().ok_or(
+                            ParseError::MalformedGir("root element expected to be repository"),
+                        )? {
+                            self.repo = Some(r);
                         }
                     }
+                    _ => self.pop()?,
+                }
+                self.path.pop();
+            }
+            Event::Empty(e) => {
+                self.path.push(segment(&e));
+                if self.stack.is_empty() {
+                    return Err(ParseError::MalformedGir("failed empty()"));
+                }
+                self.push(&e)?;
+                self.pop()?;
+                self.path.pop();
+            }
+            Event::Text(e) => {
+                if let Some(top) = self.stack.last_mut() {
+                    let content = e.xml_content(XmlVersion::Implicit1_0)?;
+                    top.text(content.as_ref())?;
                 }
-            },
-            Ok(Event::Empty(e)) => {
-                let top = stack
-                    .last_mut()
-                    .ok_or(ParseError::MalformedGir("failed empty()"))?;
-
-                let new = top.push(&e)?;
-                top.end(new)?;
             }
-            Ok(Event::Text(e)) => {
-                if let Some(top) = stack.last_mut() {
+            Event::CData(e) => {
+                if let Some(top) = self.stack.last_mut() {
                     let content = e.xml_content(XmlVersion::Implicit1_0)?;
                     top.text(content.as_ref())?;
                 }
             }
-            Ok(Event::GeneralRef(e)) => {
-                let top = stack
+            Event::GeneralRef(e) => {
+                let top = self
+                    .stack
                     .last_mut()
                     .ok_or(ParseError::MalformedGir("general ref text"))?;
 
@@ -113,14 +204,59 @@ pub fn parse(gir_contents: &str) -> Result<Repository, ParseError> {
                     .ok_or(ParseError::MalformedGir("unknown entity reference"))?;
                 top.text(text)?;
             }
-            Ok(Event::CData(_)) => {
-                todo!()
-            }
+            // comments and processing instructions carry no GIR data
+            Event::Comment(_) | Event::PI(_) | Event::Decl(_) | Event::DocType(_) => (),
+            Event::Eof => (),
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a GIR file, collecting every element and attribute that was skipped.
+/// In strict mode these are returned as errors instead.
+pub fn parse_with_options(
+    gir_contents: &str,
+    options: &ParseOptions,
+) -> Result<(Repository, Vec<ParseEvent>), ParseError> {
+    let mut reader = quick_xml::Reader::from_str(gir_contents);
+
+    let mut parser = Parser {
+        options,
+        contents: gir_contents,
+        position: 0,
+        repo: None,
+        stack: Vec::new(),
+        path: Vec::new(),
+        events: Vec::new(),
+    };
+
+    loop {
+        parser.position = reader.buffer_position();
+        match reader.read_event() {
             Ok(Event::Eof) => break,
-            Ok(_) => (),
-            Err(err) => return Err(err.into()),
+            Ok(event) => {
+                if let Err(err) = parser.handle(event) {
+                    return Err(err.at(parser.location()));
+                }
+            }
+            Err(err) => {
+                parser.position = reader.error_position();
+                return Err(ParseError::from(err).at(parser.location()));
+            }
         }
     }
 
-    repo.ok_or(ParseError::MalformedGir("missing repo"))
+    match parser.repo {
+        Some(repo) => Ok((repo, parser.events)),
+        None => Err(ParseError::MalformedGir("missing repo").at(parser.location())),
+    }
+}
+
+pub fn parse_with_events(gir_contents: &str) -> Result<(Repository, Vec<ParseEvent>), ParseError> {
+    parse_with_options(gir_contents, &ParseOptions::default())
+}
+
+pub fn parse(gir_contents: &str) -> Result<Repository, ParseError> {
+    parse_with_events(gir_contents).map(|(repo, _)| repo)
 }
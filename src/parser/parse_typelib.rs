@@ -0,0 +1,1123 @@
+//! Reader for compiled GObject introspection typelibs, see `gitypelib-internal.h`.
+//!
+//! Typelibs carry everything needed at runtime but none of the documentation
+//! and only a few of the C names, so the resulting [`Repository`] is a subset
+//! of what the matching GIR file would produce.
+
+use super::element::*;
+use super::error::ParseError;
+
+pub const MAGIC: &[u8; 16] = b"GOBJ\nMETADATA\r\n\x1a";
+const MAJOR_VERSION: u8 = 4;
+const HEADER_SIZE: usize = 112;
+const ACCESSOR_SENTINEL: u32 = 0x3ff;
+
+const BLOB_TYPE_FUNCTION: u16 = 1;
+const BLOB_TYPE_CALLBACK: u16 = 2;
+const BLOB_TYPE_STRUCT: u16 = 3;
+const BLOB_TYPE_BOXED: u16 = 4;
+const BLOB_TYPE_ENUM: u16 = 5;
+const BLOB_TYPE_FLAGS: u16 = 6;
+const BLOB_TYPE_OBJECT: u16 = 7;
+const BLOB_TYPE_INTERFACE: u16 = 8;
+const BLOB_TYPE_CONSTANT: u16 = 9;
+const BLOB_TYPE_UNION: u16 = 11;
+
+const TYPE_TAG_VOID: u32 = 0;
+const TYPE_TAG_BOOLEAN: u32 = 1;
+const TYPE_TAG_INT8: u32 = 2;
+const TYPE_TAG_UINT8: u32 = 3;
+const TYPE_TAG_INT16: u32 = 4;
+const TYPE_TAG_UINT16: u32 = 5;
+const TYPE_TAG_INT32: u32 = 6;
+const TYPE_TAG_UINT32: u32 = 7;
+const TYPE_TAG_INT64: u32 = 8;
+const TYPE_TAG_UINT64: u32 = 9;
+const TYPE_TAG_FLOAT: u32 = 10;
+const TYPE_TAG_DOUBLE: u32 = 11;
+const TYPE_TAG_UTF8: u32 = 13;
+const TYPE_TAG_FILENAME: u32 = 14;
+const TYPE_TAG_ARRAY: u32 = 15;
+const TYPE_TAG_INTERFACE: u32 = 16;
+const TYPE_TAG_ERROR: u32 = 20;
+
+fn bit(flags: u32, n: u32) -> bool {
+    flags & (1 << n) != 0
+}
+
+fn flag(flags: u32, n: u32) -> Option<bool> {
+    bit(flags, n).then_some(true)
+}
+
+fn info(deprecated: bool) -> InfoAttrs {
+    InfoAttrs {
+        introspectable: None,
+        deprecated: deprecated.then_some(true),
+        deprecated_version: None,
+        version: None,
+        stability: None,
+    }
+}
+
+fn basic_type(tag: u32, pointer: bool) -> Option<&'static str> {
+    Some(match tag {
+        TYPE_TAG_VOID if pointer => "gpointer",
+        TYPE_TAG_VOID => "none",
+        TYPE_TAG_BOOLEAN => "gboolean",
+        TYPE_TAG_INT8 => "gint8",
+        TYPE_TAG_UINT8 => "guint8",
+        TYPE_TAG_INT16 => "gint16",
+        TYPE_TAG_UINT16 => "guint16",
+        TYPE_TAG_INT32 => "gint32",
+        TYPE_TAG_UINT32 => "guint32",
+        TYPE_TAG_INT64 => "gint64",
+        TYPE_TAG_UINT64 => "guint64",
+        TYPE_TAG_FLOAT => "gfloat",
+        TYPE_TAG_DOUBLE => "gdouble",
+        12 => "GType",
+        TYPE_TAG_UTF8 => "utf8",
+        TYPE_TAG_FILENAME => "filename",
+        17 => "GLib.List",
+        18 => "GLib.SList",
+        19 => "GLib.HashTable",
+        TYPE_TAG_ERROR => "GLib.Error",
+        21 => "gunichar",
+        _ => return None,
+    })
+}
+
+fn named_type(name: String, elements: Vec<AnyType>) -> AnyType {
+    AnyType::Type(Type {
+        name: Some(name),
+        c_type: None,
+        introspectable: None,
+        doc_elements: Vec::new(),
+        elements,
+    })
+}
+
+/// Blob sizes recorded in the header, newer minor versions may append fields.
+struct Sizes {
+    entry: usize,
+    function: usize,
+    callback: usize,
+    signal: usize,
+    vfunc: usize,
+    arg: usize,
+    property: usize,
+    field: usize,
+    value: usize,
+    attribute: usize,
+    constant: usize,
+    signature: usize,
+    r#enum: usize,
+    r#struct: usize,
+    object: usize,
+    interface: usize,
+    union: usize,
+}
+
+/// Functions belonging to a type, split the way GIR lists them.
+#[derive(Default)]
+struct Callables {
+    constructors: Vec<Constructor>,
+    methods: Vec<Method>,
+    functions: Vec<Function>,
+}
+
+struct Typelib<'a> {
+    data: &'a [u8],
+    sizes: Sizes,
+}
+
+impl<'a> Typelib<'a> {
+    fn bytes(&self, offset: usize, len: usize) -> Result<&'a [u8], ParseError> {
+        self.data
+            .get(offset..offset.saturating_add(len))
+            .ok_or(ParseError::MalformedTypelib("offset out of bounds"))
+    }
+
+    fn u8(&self, offset: usize) -> Result<u8, ParseError> {
+        Ok(self.bytes(offset, 1)?[0])
+    }
+
+    fn u16(&self, offset: usize) -> Result<u16, ParseError> {
+        let b = self.bytes(offset, 2)?;
+        Ok(u16::from_le_bytes([b[0], b[1]]))
+    }
+
+    fn u32(&self, offset: usize) -> Result<u32, ParseError> {
+        let b = self.bytes(offset, 4)?;
+        Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn usize(&self, offset: usize) -> Result<usize, ParseError> {
+        Ok(self.u32(offset)? as usize)
+    }
+
+    fn size(&self, offset: usize) -> Result<usize, ParseError> {
+        Ok(self.u16(offset)? as usize)
+    }
+
+    /// Nul terminated string at the offset stored in `offset`.
+    fn string(&self, offset: usize) -> Result<String, ParseError> {
+        let start = self.usize(offset)?;
+        let rest = self
+            .data
+            .get(start..)
+            .ok_or(ParseError::MalformedTypelib("string out of bounds"))?;
+        let end = rest
+            .iter()
+            .position(|b| *b == 0)
+            .ok_or(ParseError::MalformedTypelib("unterminated string"))?;
+        Ok(String::from_utf8(rest[..end].to_vec())?)
+    }
+
+    /// Like [`Self::string`], where a zero offset means unset.
+    fn opt_string(&self, offset: usize) -> Result<Option<String>, ParseError> {
+        match self.u32(offset)? {
+            0 => Ok(None),
+            _ => self.string(offset).map(Some),
+        }
+    }
+
+    /// Name of the 1-based directory entry, qualified when it lives in another namespace.
+    fn entry_name(&self, index: u16) -> Result<String, ParseError> {
+        if index == 0 || index > self.u16(20)? {
+            return Err(ParseError::MalformedTypelib(
+                "directory index out of bounds",
+            ));
+        }
+
+        let entry = self.usize(24)? + (index as usize - 1) * self.sizes.entry;
+        let name = self.string(entry + 4)?;
+        match bit(self.u16(entry + 2)? as u32, 0) {
+            true => Ok(name),
+            false => Ok(format!("{}.{}", self.string(entry + 8)?, name)),
+        }
+    }
+
+    fn attributes(&self, blob: usize) -> Result<Vec<Attribute>, ParseError> {
+        let start = self.usize(32)?;
+        let mut attributes = Vec::new();
+
+        for i in 0..self.usize(28)? {
+            let attribute = start + i * self.sizes.attribute;
+            if self.usize(attribute)? == blob {
+                attributes.push(Attribute {
+                    name: self.string(attribute + 4)?,
+                    value: self.string(attribute + 8)?,
+                });
+            }
+        }
+
+        Ok(attributes)
+    }
+
+    fn info_elements(&self, blob: usize) -> Result<Vec<InfoElement>, ParseError> {
+        Ok(self
+            .attributes(blob)?
+            .into_iter()
+            .map(InfoElement::Annotation)
+            .collect())
+    }
+
+    fn r#type(&self, offset: usize) -> Result<AnyType, ParseError> {
+        let simple = self.u32(offset)?;
+
+        // basic types are stored inline, anything else points to a type blob
+        if simple & 0x00ff_ffff == 0 {
+            let tag = simple >> 27;
+            let name = basic_type(tag, bit(simple, 24))
+                .ok_or(ParseError::MalformedTypelib("invalid basic type tag"))?;
+            return Ok(named_type(name.to_owned(), Vec::new()));
+        }
+
+        let blob = simple as usize;
+        let flags = self.u16(blob)? as u32;
+        let tag = (flags >> 3) & 0x1f;
+
+        match tag {
+            TYPE_TAG_ARRAY => {
+                let dimension = self.u16(blob + 2)? as i32;
+                let name = match (flags >> 11) & 0x3 {
+                    0 => None,
+                    1 => Some("GLib.Array"),
+                    2 => Some("GLib.PtrArray"),
+                    _ => Some("GLib.ByteArray"),
+                };
+                Ok(AnyType::Array(Array {
+                    name: name.map(str::to_owned),
+                    c_type: None,
+                    zero_terminated: Some(bit(flags, 8)),
+                    fixed_size: bit(flags, 10).then_some(dimension),
+                    introspectable: None,
+                    length: bit(flags, 9).then_some(dimension),
+                    elements: vec![self.r#type(blob + 4)?],
+                }))
+            }
+            TYPE_TAG_INTERFACE => Ok(named_type(
+                self.entry_name(self.u16(blob + 2)?)?,
+                Vec::new(),
+            )),
+            TYPE_TAG_ERROR => Ok(named_type("GLib.Error".to_owned(), Vec::new())),
+            tag => {
+                let name = basic_type(tag, bit(flags, 0))
+                    .ok_or(ParseError::MalformedTypelib("invalid type tag"))?;
+                let elements = (0..self.size(blob + 2)?)
+                    .map(|i| self.r#type(blob + 4 + i * 4))
+                    .collect::<Result<_, _>>()?;
+                Ok(named_type(name.to_owned(), elements))
+            }
+        }
+    }
+
+    fn parameter(&self, arg: usize) -> Result<Parameter, ParseError> {
+        let flags = self.u32(arg + 4)?;
+        let closure = self.u8(arg + 8)? as i8;
+        let destroy = self.u8(arg + 9)? as i8;
+
+        let direction = match (bit(flags, 0), bit(flags, 1)) {
+            (true, true) => Some(Direction::InOut),
+            (false, true) => Some(Direction::Out),
+            _ => None,
+        };
+        let transfer_ownership = match (bit(flags, 5), bit(flags, 6)) {
+            (true, _) => TransferOwnership::Full,
+            (_, true) => TransferOwnership::Container,
+            _ => TransferOwnership::None,
+        };
+        let scope = match (flags >> 8) & 0x7 {
+            1 => Some(Scope::Call),
+            2 => Some(Scope::Async),
+            3 => Some(Scope::Notified),
+            4 => Some(Scope::Forever),
+            _ => None,
+        };
+
+        Ok(Parameter {
+            name: Some(self.string(arg)?),
+            nullable: flag(flags, 3),
+            allow_none: None,
+            introspectable: None,
+            closure: (closure >= 0).then_some(closure as i32),
+            destroy: (destroy >= 0).then_some(destroy as i32),
+            scope,
+            caller_allocates: direction.is_some().then_some(bit(flags, 2)),
+            direction,
+            optional: flag(flags, 4),
+            skip: flag(flags, 11),
+            transfer_ownership: Some(transfer_ownership),
+            doc_elements: Vec::new(),
+            varargs: None,
+            r#type: Some(self.r#type(arg + 12)?),
+            annotations: self.attributes(arg)?,
+        })
+    }
+
+    /// Parameters, return value and whether the callable throws.
+    fn signature(
+        &self,
+        offset: usize,
+        instance: Option<&str>,
+    ) -> Result<(Parameters, ReturnValue, bool), ParseError> {
+        let signature = self.usize(offset)?;
+        let flags = self.u16(signature + 4)? as u32;
+
+        let instance_parameter = instance.map(|name| InstanceParameter {
+            name: "self".to_owned(),
+            nullable: None,
+            allow_none: None,
+            direction: None,
+            caller_allocates: None,
+            transfer_ownership: Some(match bit(flags, 4) {
+                true => TransferOwnership::Full,
+                false => TransferOwnership::None,
+            }),
+            r#type: Some(named_type(name.to_owned(), Vec::new())),
+            doc_elements: Vec::new(),
+        });
+
+        let parameters = (0..self.size(signature + 6)?)
+            .map(|i| self.parameter(signature + self.sizes.signature + i * self.sizes.arg))
+            .collect::<Result<_, _>>()?;
+
+        let transfer_ownership = match (bit(flags, 1), bit(flags, 2)) {
+            (true, _) => TransferOwnership::Full,
+            (_, true) => TransferOwnership::Container,
+            _ => TransferOwnership::None,
+        };
+
+        let return_value = ReturnValue {
+            introspectable: None,
+            nullable: flag(flags, 0),
+            closure: None,
+            scope: None,
+            destroy: None,
+            skip: flag(flags, 3),
+            allow_none: None,
+            transfer_ownership: Some(transfer_ownership),
+            doc_elements: Vec::new(),
+            annotations: Vec::new(),
+            r#type: Some(self.r#type(signature)?),
+        };
+
+        Ok((
+            Parameters {
+                instance_parameter,
+                parameters,
+            },
+            return_value,
+            bit(flags, 5),
+        ))
+    }
+
+    fn callable_attrs(
+        &self,
+        name: String,
+        symbol: Option<String>,
+        deprecated: bool,
+        throws: bool,
+    ) -> CallableAttrs {
+        CallableAttrs {
+            info: info(deprecated),
+            name,
+            c_identifier: symbol,
+            shadowed_by: None,
+            shadows: None,
+            throws: throws.then_some(true),
+            moved_to: None,
+            glib_async_func: None,
+            glib_sync_func: None,
+            glib_finish_func: None,
+        }
+    }
+
+    /// Function blobs of a type, properties are needed to resolve accessors.
+    fn callables(
+        &self,
+        offset: usize,
+        count: usize,
+        container: &str,
+        properties: &[Property],
+    ) -> Result<Callables, ParseError> {
+        let mut callables = Callables::default();
+
+        for i in 0..count {
+            let blob = offset + i * self.sizes.function;
+            let flags = self.u16(blob + 2)? as u32;
+            let is_static = bit(self.u16(blob + 16)? as u32, 0);
+            let constructor = bit(flags, 3);
+            let is_method = !constructor && !is_static;
+
+            let (parameters, return_value, throws) =
+                self.signature(blob + 12, is_method.then_some(container))?;
+            let attrs = self.callable_attrs(
+                self.string(blob + 4)?,
+                Some(self.string(blob + 8)?),
+                bit(flags, 0),
+                throws || bit(flags, 5),
+            );
+            let info_elements = self.info_elements(blob)?;
+
+            if constructor {
+                callables.constructors.push(Constructor {
+                    attrs,
+                    info_elements,
+                    parameters: Some(parameters),
+                    return_value: Some(return_value),
+                });
+            } else if is_static {
+                callables.functions.push(Function {
+                    attrs,
+                    info_elements,
+                    parameters: Some(parameters),
+                    return_value: Some(return_value),
+                });
+            } else {
+                let property = |n: u32| {
+                    properties
+                        .get(((flags >> 6) & ACCESSOR_SENTINEL) as usize)
+                        .filter(|_| bit(flags, n))
+                        .map(|p| p.name.clone())
+                };
+                callables.methods.push(Method {
+                    attrs,
+                    glib_set_property: property(1),
+                    glib_get_property: property(2),
+                    info_elements,
+                    parameters: Some(parameters),
+                    return_value: Some(return_value),
+                });
+            }
+        }
+
+        Ok(callables)
+    }
+
+    fn functions(&self, offset: usize, count: usize) -> Result<Vec<Function>, ParseError> {
+        // without a container every function is static
+        (0..count)
+            .map(|i| {
+                let blob = offset + i * self.sizes.function;
+                let flags = self.u16(blob + 2)? as u32;
+                let (parameters, return_value, throws) = self.signature(blob + 12, None)?;
+
+                Ok(Function {
+                    attrs: self.callable_attrs(
+                        self.string(blob + 4)?,
+                        Some(self.string(blob + 8)?),
+                        bit(flags, 0),
+                        throws || bit(flags, 5),
+                    ),
+                    info_elements: self.info_elements(blob)?,
+                    parameters: Some(parameters),
+                    return_value: Some(return_value),
+                })
+            })
+            .collect()
+    }
+
+    fn callback(&self, blob: usize) -> Result<Callback, ParseError> {
+        let (parameters, return_value, throws) = self.signature(blob + 8, None)?;
+
+        Ok(Callback {
+            info: info(bit(self.u16(blob + 2)? as u32, 0)),
+            name: self.string(blob + 4)?,
+            c_type: None,
+            throws: throws.then_some(true),
+            info_elements: self.info_elements(blob)?,
+            parameter: Some(parameters),
+            return_value: Some(return_value),
+        })
+    }
+
+    /// Fields followed by the offset past them, as embedded callbacks are stored inline.
+    fn fields(&self, mut offset: usize, count: usize) -> Result<(Vec<Field>, usize), ParseError> {
+        let mut fields = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            let blob = offset;
+            let flags = self.u8(blob + 4)? as u32;
+            let bits = self.u8(blob + 5)?;
+            offset += self.sizes.field;
+
+            let (callback, r#type) = match bit(flags, 2) {
+                true => {
+                    offset += self.sizes.callback;
+                    (Some(self.callback(blob + self.sizes.field)?), None)
+                }
+                false => (None, Some(self.r#type(blob + 12)?)),
+            };
+
+            fields.push(Field {
+                info: info(false),
+                name: self.string(blob)?,
+                writable: flag(flags, 1),
+                readable: Some(bit(flags, 0)),
+                private: None,
+                bits: (bits > 0).then_some(bits as i32),
+                transfer_ownership: None,
+                info_elements: self.info_elements(blob)?,
+                callback,
+                r#type,
+            });
+        }
+
+        Ok((fields, offset))
+    }
+
+    fn properties(&self, offset: usize, count: usize) -> Result<Vec<Property>, ParseError> {
+        (0..count)
+            .map(|i| {
+                let blob = offset + i * self.sizes.property;
+                let flags = self.u32(blob + 4)?;
+
+                let transfer_ownership = match (bit(flags, 5), bit(flags, 6)) {
+                    (true, _) => TransferOwnership::Full,
+                    (_, true) => TransferOwnership::Container,
+                    _ => TransferOwnership::None,
+                };
+
+                Ok(Property {
+                    info: info(bit(flags, 0)),
+                    name: self.string(blob)?,
+                    writable: flag(flags, 2),
+                    readable: Some(bit(flags, 1)),
+                    construct: flag(flags, 3),
+                    construct_only: flag(flags, 4),
+                    setter: None,
+                    getter: None,
+                    default_value: None,
+                    transfer_ownership: Some(transfer_ownership),
+                    r#type: Some(self.r#type(blob + 12)?),
+                    info_elements: self.info_elements(blob)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Resolve property accessors once the methods are known.
+    fn accessors(
+        &self,
+        offset: usize,
+        properties: &mut [Property],
+        methods_offset: usize,
+    ) -> Result<(), ParseError> {
+        for (i, property) in properties.iter_mut().enumerate() {
+            let flags = self.u32(offset + i * self.sizes.property + 4)?;
+            let method = |index: u32| match index {
+                ACCESSOR_SENTINEL => Ok(None),
+                index => self
+                    .string(methods_offset + index as usize * self.sizes.function + 4)
+                    .map(Some),
+            };
+            property.setter = method((flags >> 7) & ACCESSOR_SENTINEL)?;
+            property.getter = method((flags >> 17) & ACCESSOR_SENTINEL)?;
+        }
+        Ok(())
+    }
+
+    fn signals(
+        &self,
+        offset: usize,
+        count: usize,
+        container: &str,
+    ) -> Result<Vec<Signal>, ParseError> {
+        (0..count)
+            .map(|i| {
+                let blob = offset + i * self.sizes.signal;
+                let flags = self.u16(blob)? as u32;
+                let (mut parameters, return_value, _) =
+                    self.signature(blob + 12, Some(container))?;
+                parameters.instance_parameter = None;
+
+                let when = match (bit(flags, 1), bit(flags, 2), bit(flags, 3)) {
+                    (true, _, _) => Some(SignalWhen::First),
+                    (_, true, _) => Some(SignalWhen::Last),
+                    (_, _, true) => Some(SignalWhen::Cleanup),
+                    _ => None,
+                };
+
+                Ok(Signal {
+                    info: info(bit(flags, 0)),
+                    name: self.string(blob + 4)?,
+                    detailed: flag(flags, 5),
+                    when,
+                    action: flag(flags, 6),
+                    no_hooks: flag(flags, 7),
+                    no_recurse: flag(flags, 4),
+                    emitter: None,
+                    info_elements: self.info_elements(blob)?,
+                    parameters: Some(parameters),
+                    return_value: Some(return_value),
+                })
+            })
+            .collect()
+    }
+
+    fn virtual_methods(
+        &self,
+        offset: usize,
+        count: usize,
+        container: &str,
+        methods_offset: usize,
+    ) -> Result<Vec<VirtualMethod>, ParseError> {
+        (0..count)
+            .map(|i| {
+                let blob = offset + i * self.sizes.vfunc;
+                let flags = self.u16(blob + 4)? as u32;
+                let invoker = match self.u16(blob + 10)? as u32 & ACCESSOR_SENTINEL {
+                    ACCESSOR_SENTINEL => None,
+                    index => Some(
+                        self.string(methods_offset + index as usize * self.sizes.function + 4)?,
+                    ),
+                };
+                let (parameters, return_value, throws) =
+                    self.signature(blob + 16, Some(container))?;
+
+                Ok(VirtualMethod {
+                    attrs: self.callable_attrs(
+                        self.string(blob)?,
+                        None,
+                        false,
+                        throws || bit(flags, 4),
+                    ),
+                    invoker,
+                    info_elements: self.info_elements(blob)?,
+                    parameters: Some(parameters),
+                    return_value: Some(return_value),
+                })
+            })
+            .collect()
+    }
+
+    fn constant_value(
+        &self,
+        r#type: &AnyType,
+        offset: usize,
+        size: usize,
+    ) -> Result<String, ParseError> {
+        let bytes = self.bytes(offset, size)?;
+        let name = match r#type {
+            AnyType::Type(Type {
+                name: Some(name), ..
+            }) => name.as_str(),
+            _ => return Ok(String::new()),
+        };
+
+        let int = |signed: bool| -> String {
+            let mut buf = [0u8; 8];
+            let len = bytes.len().min(8);
+            buf[..len].copy_from_slice(&bytes[..len]);
+            let value = u64::from_le_bytes(buf);
+            match (signed, len) {
+                (true, 1) => (value as i8).to_string(),
+                (true, 2) => (value as i16).to_string(),
+                (true, 4) => (value as i32).to_string(),
+                (true, _) => (value as i64).to_string(),
+                (false, _) => value.to_string(),
+            }
+        };
+
+        Ok(match name {
+            "gboolean" => (int(false) != "0").to_string(),
+            "gint8" | "gint16" | "gint32" | "gint64" => int(true),
+            "guint8" | "guint16" | "guint32" | "guint64" => int(false),
+            "gfloat" if size == 4 => {
+                f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).to_string()
+            }
+            "gdouble" if size == 8 => {
+                let mut buf = [0u8; 8];
+                buf.copy_from_slice(bytes);
+                f64::from_le_bytes(buf).to_string()
+            }
+            _ => String::from_utf8_lossy(bytes.strip_suffix(&[0]).unwrap_or(bytes)).into_owned(),
+        })
+    }
+
+    fn constant(&self, blob: usize) -> Result<Constant, ParseError> {
+        let r#type = self.r#type(blob + 8)?;
+        let value = self.constant_value(&r#type, self.usize(blob + 16)?, self.usize(blob + 12)?)?;
+
+        Ok(Constant {
+            info: info(bit(self.u16(blob + 2)? as u32, 0)),
+            name: self.string(blob + 4)?,
+            value,
+            c_type: None,
+            c_identifier: None,
+            r#type: Some(r#type),
+            info_elements: self.info_elements(blob)?,
+        })
+    }
+
+    fn constants(&self, offset: usize, count: usize) -> Result<Vec<Constant>, ParseError> {
+        (0..count)
+            .map(|i| self.constant(offset + i * self.sizes.constant))
+            .collect()
+    }
+
+    fn members(&self, offset: usize, count: usize) -> Result<Vec<Member>, ParseError> {
+        (0..count)
+            .map(|i| {
+                let blob = offset + i * self.sizes.value;
+                let flags = self.u32(blob)?;
+                let value = self.u32(blob + 8)?;
+
+                Ok(Member {
+                    info: info(bit(flags, 0)),
+                    name: self.string(blob + 4)?,
+                    value: match bit(flags, 1) {
+                        true => value.to_string(),
+                        false => (value as i32).to_string(),
+                    },
+                    // typelibs do not keep the C names of members
+                    c_identifier: String::new(),
+                    glib_nick: None,
+                    glib_name: None,
+                    info_elements: self.info_elements(blob)?,
+                })
+            })
+            .collect()
+    }
+
+    fn enumeration(&self, blob: usize, c_prefix: &str) -> Result<Enumeration, ParseError> {
+        let name = self.string(blob + 4)?;
+        let glib_type_name = self.opt_string(blob + 8)?;
+        let n_values = self.size(blob + 16)?;
+        let members_offset = blob + self.sizes.r#enum;
+        let functions_offset = members_offset + n_values * self.sizes.value;
+
+        Ok(Enumeration {
+            info: info(bit(self.u16(blob + 2)? as u32, 0)),
+            c_type: glib_type_name
+                .clone()
+                .unwrap_or_else(|| format!("{c_prefix}{name}")),
+            name,
+            glib_type_name,
+            glib_get_type: self.opt_string(blob + 12)?,
+            glib_error_domain: self.opt_string(blob + 20)?,
+            info_elements: self.info_elements(blob)?,
+            members: self.members(members_offset, n_values)?,
+            functions: self.functions(functions_offset, self.size(blob + 18)?)?,
+            inline_functions: Vec::new(),
+        })
+    }
+
+    fn bitfield(&self, blob: usize, c_prefix: &str) -> Result<Bitfield, ParseError> {
+        let Enumeration {
+            info,
+            name,
+            c_type,
+            glib_type_name,
+            glib_get_type,
+            info_elements,
+            members,
+            functions,
+            ..
+        } = self.enumeration(blob, c_prefix)?;
+
+        Ok(Bitfield {
+            info,
+            name,
+            c_type,
+            glib_type_name,
+            glib_get_type,
+            info_elements,
+            members,
+            functions,
+            inline_functions: Vec::new(),
+        })
+    }
+
+    fn record(&self, blob: usize) -> Result<Record, ParseError> {
+        let flags = self.u16(blob + 2)? as u32;
+        let name = self.string(blob + 4)?;
+        let glib_type_name = self.opt_string(blob + 8)?;
+        let (fields, methods_offset) =
+            self.fields(blob + self.sizes.r#struct, self.size(blob + 20)?)?;
+        let callables = self.callables(methods_offset, self.size(blob + 22)?, &name, &[])?;
+
+        Ok(Record {
+            info: info(bit(flags, 0)),
+            c_type: glib_type_name.clone(),
+            name,
+            disguised: None,
+            opaque: None,
+            pointer: None,
+            glib_type_name,
+            glib_get_type: self.opt_string(blob + 12)?,
+            c_symbol_prefix: None,
+            foreign: flag(flags, 9),
+            // filled in from the owning class or interface
+            glib_is_gtype_struct_for: None,
+            copy_function: self.opt_string(blob + 24)?,
+            free_function: self.opt_string(blob + 28)?,
+            info_elements: self.info_elements(blob)?,
+            fields,
+            functions: callables.functions,
+            inline_functions: Vec::new(),
+            unions: Vec::new(),
+            methods: callables.methods,
+            inline_methods: Vec::new(),
+            constructors: callables.constructors,
+        })
+    }
+
+    fn boxed(&self, blob: usize) -> Result<Boxed, ParseError> {
+        let name = self.string(blob + 4)?;
+        let (_, methods_offset) = self.fields(blob + self.sizes.r#struct, self.size(blob + 20)?)?;
+
+        Ok(Boxed {
+            info: info(bit(self.u16(blob + 2)? as u32, 0)),
+            glib_type_name: self.opt_string(blob + 8)?,
+            glib_get_type: self.opt_string(blob + 12)?,
+            c_symbol_prefix: None,
+            info_elements: self.info_elements(blob)?,
+            functions: self.functions(methods_offset, self.size(blob + 22)?)?,
+            inline_functions: Vec::new(),
+            glib_name: name,
+        })
+    }
+
+    fn union(&self, blob: usize) -> Result<Union, ParseError> {
+        let name = self.string(blob + 4)?;
+        let glib_type_name = self.opt_string(blob + 8)?;
+        let (fields, functions_offset) =
+            self.fields(blob + self.sizes.union, self.size(blob + 20)?)?;
+        let callables = self.callables(functions_offset, self.size(blob + 22)?, &name, &[])?;
+
+        Ok(Union {
+            info: info(bit(self.u16(blob + 2)? as u32, 0)),
+            name: Some(name),
+            c_type: glib_type_name.clone(),
+            c_symbol_prefix: None,
+            glib_type_name,
+            glib_get_type: self.opt_string(blob + 12)?,
+            copy_function: self.opt_string(blob + 24)?,
+            free_function: self.opt_string(blob + 28)?,
+            info_elements: self.info_elements(blob)?,
+            fields,
+            constructors: callables.constructors,
+            methods: callables.methods,
+            inline_methods: Vec::new(),
+            functions: callables.functions,
+            inline_functions: Vec::new(),
+            records: Vec::new(),
+        })
+    }
+
+    fn entries(&self, offset: usize, count: usize) -> Result<Vec<String>, ParseError> {
+        (0..count)
+            .map(|i| self.entry_name(self.u16(offset + i * 2)?))
+            .collect()
+    }
+
+    fn class(&self, blob: usize) -> Result<Class, ParseError> {
+        let flags = self.u16(blob + 2)? as u32;
+        let name = self.string(blob + 4)?;
+        let glib_type_name = self.string(blob + 8)?;
+        let count = |field: usize| self.size(blob + field);
+
+        let parent = self.u16(blob + 16)?;
+        let type_struct = self.u16(blob + 18)?;
+        let n_interfaces = count(20)?;
+
+        let fields_offset = blob + self.sizes.object + (n_interfaces + n_interfaces % 2) * 2;
+        let (fields, properties_offset) = self.fields(fields_offset, count(22)?)?;
+        let methods_offset = properties_offset + count(24)? * self.sizes.property;
+        let signals_offset = methods_offset + count(26)? * self.sizes.function;
+        let vfuncs_offset = signals_offset + count(28)? * self.sizes.signal;
+        let constants_offset = vfuncs_offset + count(30)? * self.sizes.vfunc;
+
+        let mut properties = self.properties(properties_offset, count(24)?)?;
+        self.accessors(properties_offset, &mut properties, methods_offset)?;
+        let callables = self.callables(methods_offset, count(26)?, &name, &properties)?;
+
+        Ok(Class {
+            info: info(bit(flags, 0)),
+            c_type: Some(glib_type_name.clone()),
+            glib_type_name,
+            glib_get_type: self.string(blob + 12)?,
+            parent: (parent != 0).then(|| self.entry_name(parent)).transpose()?,
+            glib_type_struct: (type_struct != 0)
+                .then(|| self.entry_name(type_struct))
+                .transpose()?,
+            glib_ref_func: self.opt_string(blob + 36)?,
+            glib_unref_func: self.opt_string(blob + 40)?,
+            glib_set_value_func: self.opt_string(blob + 44)?,
+            glib_get_value_func: self.opt_string(blob + 48)?,
+            c_symbol_prefix: None,
+            r#abstract: flag(flags, 1),
+            r#final: flag(flags, 3),
+            glib_fundamental: flag(flags, 2),
+            info_elements: self.info_elements(blob)?,
+            implements: self
+                .entries(blob + self.sizes.object, n_interfaces)?
+                .into_iter()
+                .map(|name| Implements { name })
+                .collect(),
+            constructors: callables.constructors,
+            methods: callables.methods,
+            inline_methods: Vec::new(),
+            functions: callables.functions,
+            inline_functions: Vec::new(),
+            virtual_methods: self.virtual_methods(
+                vfuncs_offset,
+                count(30)?,
+                &name,
+                methods_offset,
+            )?,
+            fields,
+            signals: self.signals(signals_offset, count(28)?, &name)?,
+            properties,
+            unions: Vec::new(),
+            constants: self.constants(constants_offset, count(32)?)?,
+            records: Vec::new(),
+            callbacks: Vec::new(),
+            name,
+        })
+    }
+
+    fn interface(&self, blob: usize) -> Result<Interface, ParseError> {
+        let name = self.string(blob + 4)?;
+        let glib_type_name = self.string(blob + 8)?;
+        let count = |field: usize| self.size(blob + field);
+
+        let type_struct = self.u16(blob + 16)?;
+        let n_prerequisites = count(18)?;
+
+        let properties_offset =
+            blob + self.sizes.interface + (n_prerequisites + n_prerequisites % 2) * 2;
+        let methods_offset = properties_offset + count(20)? * self.sizes.property;
+        let signals_offset = methods_offset + count(22)? * self.sizes.function;
+        let vfuncs_offset = signals_offset + count(24)? * self.sizes.signal;
+        let constants_offset = vfuncs_offset + count(26)? * self.sizes.vfunc;
+
+        let mut properties = self.properties(properties_offset, count(20)?)?;
+        self.accessors(properties_offset, &mut properties, methods_offset)?;
+        let callables = self.callables(methods_offset, count(22)?, &name, &properties)?;
+
+        Ok(Interface {
+            info: info(bit(self.u16(blob + 2)? as u32, 0)),
+            c_type: Some(glib_type_name.clone()),
+            glib_type_name,
+            glib_get_type: self.string(blob + 12)?,
+            c_symbol_prefix: None,
+            glib_type_struct: (type_struct != 0)
+                .then(|| self.entry_name(type_struct))
+                .transpose()?,
+            info_elements: self.info_elements(blob)?,
+            prerequisites: self
+                .entries(blob + self.sizes.interface, n_prerequisites)?
+                .into_iter()
+                .map(|name| Prerequisite { name })
+                .collect(),
+            implements: Vec::new(),
+            functions: callables.functions,
+            inline_functions: Vec::new(),
+            constructors: callables.constructors,
+            methods: callables.methods,
+            inline_methods: Vec::new(),
+            virtual_methods: self.virtual_methods(
+                vfuncs_offset,
+                count(26)?,
+                &name,
+                methods_offset,
+            )?,
+            fields: Vec::new(),
+            signals: self.signals(signals_offset, count(24)?, &name)?,
+            properties,
+            callbacks: Vec::new(),
+            constants: self.constants(constants_offset, count(28)?)?,
+            name,
+        })
+    }
+
+    fn namespace(&self) -> Result<Namespace, ParseError> {
+        let c_prefix = self.opt_string(56)?;
+        let first_prefix = c_prefix
+            .as_deref()
+            .and_then(|p| p.split(',').next())
+            .unwrap_or_default()
+            .to_owned();
+
+        let mut ns = Namespace {
+            name: self.string(44)?,
+            version: self.string(48)?,
+            // typelibs only store identifier prefixes, symbols usually follow them in lower case
+            c_symbol_prefixes: c_prefix.as_ref().map(|p| p.to_lowercase()),
+            c_identifier_prefixes: c_prefix,
+            c_prefix: None,
+            shared_library: self.opt_string(52)?,
+            aliases: Vec::new(),
+            classes: Vec::new(),
+            interfaces: Vec::new(),
+            records: Vec::new(),
+            enums: Vec::new(),
+            functions: Vec::new(),
+            inline_functions: Vec::new(),
+            macro_functions: Vec::new(),
+            unions: Vec::new(),
+            bitfields: Vec::new(),
+            callbacks: Vec::new(),
+            constants: Vec::new(),
+            annotations: Vec::new(),
+            boxeds: Vec::new(),
+            doc_sections: Vec::new(),
+        };
+
+        let directory = self.usize(24)?;
+        for i in 0..self.size(22)? {
+            let entry = directory + i * self.sizes.entry;
+            let blob = self.usize(entry + 8)?;
+
+            match self.u16(entry)? {
+                BLOB_TYPE_FUNCTION => ns.functions.extend(self.functions(blob, 1)?),
+                BLOB_TYPE_CALLBACK => ns.callbacks.push(self.callback(blob)?),
+                BLOB_TYPE_STRUCT => ns.records.push(self.record(blob)?),
+                BLOB_TYPE_BOXED => ns.boxeds.push(self.boxed(blob)?),
+                BLOB_TYPE_ENUM => ns.enums.push(self.enumeration(blob, &first_prefix)?),
+                BLOB_TYPE_FLAGS => ns.bitfields.push(self.bitfield(blob, &first_prefix)?),
+                BLOB_TYPE_OBJECT => ns.classes.push(self.class(blob)?),
+                BLOB_TYPE_INTERFACE => ns.interfaces.push(self.interface(blob)?),
+                BLOB_TYPE_CONSTANT => ns.constants.push(self.constant(blob)?),
+                BLOB_TYPE_UNION => ns.unions.push(self.union(blob)?),
+                _ => return Err(ParseError::MalformedTypelib("unknown blob type")),
+            }
+        }
+
+        let type_structs = ns
+            .classes
+            .iter()
+            .map(|c| (&c.glib_type_struct, &c.name))
+            .chain(ns.interfaces.iter().map(|i| (&i.glib_type_struct, &i.name)))
+            .filter_map(|(s, name)| Some((s.clone()?, name.clone())))
+            .collect::<Vec<_>>();
+
+        for (type_struct, name) in type_structs {
+            if let Some(record) = ns.records.iter_mut().find(|r| r.name == type_struct) {
+                record.glib_is_gtype_struct_for = Some(name);
+            }
+        }
+
+        Ok(ns)
+    }
+}
+
+/// Parse a compiled typelib, documentation and most C names are not part of the format.
+pub fn parse(data: &[u8]) -> Result<Repository, ParseError> {
+    if data.len() < HEADER_SIZE || !data.starts_with(MAGIC) {
+        return Err(ParseError::MalformedTypelib("invalid magic"));
+    }
+    if data[16] != MAJOR_VERSION {
+        return Err(ParseError::MalformedTypelib("unsupported version"));
+    }
+
+    let size = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]) as usize;
+    let typelib = Typelib {
+        data,
+        sizes: Sizes {
+            entry: size(60),
+            function: size(62),
+            callback: size(64),
+            signal: size(66),
+            vfunc: size(68),
+            arg: size(70),
+            property: size(72),
+            field: size(74),
+            value: size(76),
+            attribute: size(78),
+            constant: size(80),
+            signature: size(84),
+            r#enum: size(86),
+            r#struct: size(88),
+            object: size(90),
+            interface: size(92),
+            union: size(94),
+        },
+    };
+
+    let includes = match typelib.opt_string(36)? {
+        None => Vec::new(),
+        Some(dependencies) => dependencies
+            .split('|')
+            .filter_map(|dependency| dependency.rsplit_once('-'))
+            .map(|(name, version)| Include {
+                name: name.to_owned(),
+                version: version.to_owned(),
+            })
+            .collect(),
+    };
+
+    Ok(Repository {
+        version: None,
+        c_identifier_prefixes: None,
+        c_symbol_prefixes: None,
+        includes,
+        c_includes: Vec::new(),
+        packages: Vec::new(),
+        namespaces: vec![typelib.namespace()?],
+        doc_formats: Vec::new(),
+    })
+}
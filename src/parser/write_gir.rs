@@ -0,0 +1,886 @@
+use super::element::*;
+use quick_xml::Writer;
+use quick_xml::events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event};
+use std::io;
+
+const CORE_NS: &str = "http://www.gtk.org/introspection/core/1.0";
+const C_NS: &str = "http://www.gtk.org/introspection/c/1.0";
+const GLIB_NS: &str = "http://www.gtk.org/introspection/glib/1.0";
+const DOC_NS: &str = "http://www.gtk.org/introspection/doc/1.0";
+
+trait AttrValue {
+    fn attr_value(&self) -> String;
+}
+
+impl AttrValue for str {
+    fn attr_value(&self) -> String {
+        self.to_owned()
+    }
+}
+
+impl AttrValue for String {
+    fn attr_value(&self) -> String {
+        self.clone()
+    }
+}
+
+impl AttrValue for i32 {
+    fn attr_value(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl AttrValue for bool {
+    fn attr_value(&self) -> String {
+        String::from(if *self { "1" } else { "0" })
+    }
+}
+
+macro_rules! attr_value_as_str {
+    ($($name:ident),*) => {$(
+        impl AttrValue for $name {
+            fn attr_value(&self) -> String {
+                self.as_str().to_owned()
+            }
+        }
+    )*};
+}
+
+attr_value_as_str!(
+    Direction,
+    Scope,
+    Stability,
+    SignalWhen,
+    DocFormatKind,
+    TransferOwnership
+);
+
+struct Node {
+    tag: &'static str,
+    attrs: Vec<(&'static str, String)>,
+    children: Vec<Node>,
+    text: Option<String>,
+}
+
+impl Node {
+    fn new(tag: &'static str) -> Self {
+        Self {
+            tag,
+            attrs: Vec::new(),
+            children: Vec::new(),
+            text: None,
+        }
+    }
+
+    fn attr<V: AttrValue + ?Sized>(mut self, key: &'static str, value: Option<&V>) -> Self {
+        if let Some(value) = value {
+            self.attrs.push((key, value.attr_value()));
+        }
+        self
+    }
+
+    fn info(self, info: &InfoAttrs) -> Self {
+        self.attr("introspectable", info.introspectable.as_ref())
+            .attr("deprecated", info.deprecated.as_ref())
+            .attr("deprecated-version", info.deprecated_version.as_ref())
+            .attr("version", info.version.as_ref())
+            .attr("stability", info.stability.as_ref())
+    }
+
+    fn callable(self, attrs: &CallableAttrs) -> Self {
+        self.attr("name", Some(&attrs.name))
+            .attr("c:identifier", attrs.c_identifier.as_ref())
+            .attr("shadowed-by", attrs.shadowed_by.as_ref())
+            .attr("shadows", attrs.shadows.as_ref())
+            .attr("throws", attrs.throws.as_ref())
+            .attr("moved-to", attrs.moved_to.as_ref())
+            .attr("glib:async-func", attrs.glib_async_func.as_ref())
+            .attr("glib:sync-func", attrs.glib_sync_func.as_ref())
+            .attr("glib:finish-func", attrs.glib_finish_func.as_ref())
+            .info(&attrs.info)
+    }
+
+    fn child<T: ToNode>(mut self, child: Option<&T>) -> Self {
+        self.children.extend(child.map(ToNode::to_node));
+        self
+    }
+
+    fn children<T: ToNode>(mut self, children: &[T]) -> Self {
+        self.children.extend(children.iter().map(ToNode::to_node));
+        self
+    }
+
+    fn text(mut self, text: &str) -> Self {
+        self.text = Some(text.to_owned());
+        self
+    }
+
+    fn write<W: io::Write>(&self, writer: &mut Writer<W>) -> io::Result<()> {
+        let mut start = BytesStart::new(self.tag);
+        for (key, value) in &self.attrs {
+            start.push_attribute((*key, value.as_str()));
+        }
+
+        if self.children.is_empty() && self.text.is_none() {
+            return writer.write_event(Event::Empty(start));
+        }
+
+        writer.write_event(Event::Start(start))?;
+        if let Some(text) = &self.text {
+            writer.write_event(Event::Text(BytesText::new(text)))?;
+        }
+        for child in &self.children {
+            child.write(writer)?;
+        }
+        writer.write_event(Event::End(BytesEnd::new(self.tag)))
+    }
+}
+
+trait ToNode {
+    fn to_node(&self) -> Node;
+}
+
+impl ToNode for Repository {
+    fn to_node(&self) -> Node {
+        let mut node = Node::new("repository")
+            .attr("version", self.version.as_ref())
+            .attr("xmlns", Some(CORE_NS))
+            .attr("xmlns:c", Some(C_NS))
+            .attr("xmlns:glib", Some(GLIB_NS));
+
+        if !self.doc_formats.is_empty() {
+            node = node.attr("xmlns:doc", Some(DOC_NS));
+        }
+
+        node.attr("c:identifier-prefixes", self.c_identifier_prefixes.as_ref())
+            .attr("c:symbol-prefixes", self.c_symbol_prefixes.as_ref())
+            .children(&self.includes)
+            .children(&self.packages)
+            .children(&self.c_includes)
+            .children(&self.doc_formats)
+            .children(&self.namespaces)
+    }
+}
+
+impl ToNode for Include {
+    fn to_node(&self) -> Node {
+        Node::new("include")
+            .attr("name", Some(&self.name))
+            .attr("version", Some(&self.version))
+    }
+}
+
+impl ToNode for CInclude {
+    fn to_node(&self) -> Node {
+        Node::new("c:include").attr("name", Some(&self.name))
+    }
+}
+
+impl ToNode for Package {
+    fn to_node(&self) -> Node {
+        Node::new("package").attr("name", Some(&self.name))
+    }
+}
+
+impl ToNode for DocFormat {
+    fn to_node(&self) -> Node {
+        Node::new("doc:format").attr("name", Some(&self.name))
+    }
+}
+
+impl ToNode for Namespace {
+    fn to_node(&self) -> Node {
+        Node::new("namespace")
+            .attr("name", Some(&self.name))
+            .attr("version", Some(&self.version))
+            .attr("shared-library", self.shared_library.as_ref())
+            .attr("c:identifier-prefixes", self.c_identifier_prefixes.as_ref())
+            .attr("c:symbol-prefixes", self.c_symbol_prefixes.as_ref())
+            .attr("c:prefix", self.c_prefix.as_ref())
+            .children(&self.annotations)
+            .children(&self.doc_sections)
+            .children(&self.aliases)
+            .children(&self.constants)
+            .children(&self.bitfields)
+            .children(&self.enums)
+            .children(&self.callbacks)
+            .children(&self.boxeds)
+            .children(&self.records)
+            .children(&self.unions)
+            .children(&self.interfaces)
+            .children(&self.classes)
+            .children(&self.functions)
+            .children(&self.inline_functions)
+            .children(&self.macro_functions)
+    }
+}
+
+impl ToNode for InfoElement {
+    fn to_node(&self) -> Node {
+        match self {
+            InfoElement::Annotation(a) => a.to_node(),
+            InfoElement::DocElement(d) => d.to_node(),
+        }
+    }
+}
+
+impl ToNode for DocElement {
+    fn to_node(&self) -> Node {
+        match self {
+            DocElement::DocVersion(d) => d.to_node(),
+            DocElement::DocStability(d) => d.to_node(),
+            DocElement::Doc(d) => d.to_node(),
+            DocElement::DocDeprecated(d) => d.to_node(),
+            DocElement::SourcePosition(d) => d.to_node(),
+        }
+    }
+}
+
+impl ToNode for AnyType {
+    fn to_node(&self) -> Node {
+        match self {
+            AnyType::Type(t) => t.to_node(),
+            AnyType::Array(a) => a.to_node(),
+        }
+    }
+}
+
+impl ToNode for Attribute {
+    fn to_node(&self) -> Node {
+        Node::new("attribute")
+            .attr("name", Some(&self.name))
+            .attr("value", Some(&self.value))
+    }
+}
+
+impl ToNode for Doc {
+    fn to_node(&self) -> Node {
+        Node::new("doc")
+            .attr("xml:space", self.xml_space.as_ref())
+            .attr("xml:whitespace", self.xml_whitespace.as_ref())
+            .attr("filename", self.filename.as_ref())
+            .attr("line", self.line.as_ref())
+            .attr("column", self.column.as_ref())
+            .text(&self.text)
+    }
+}
+
+impl ToNode for DocVersion {
+    fn to_node(&self) -> Node {
+        Node::new("doc-version")
+            .attr("xml:space", self.xml_space.as_ref())
+            .attr("xml:whitespace", self.xml_whitespace.as_ref())
+            .text(&self.text)
+    }
+}
+
+impl ToNode for DocStability {
+    fn to_node(&self) -> Node {
+        Node::new("doc-stability")
+            .attr("xml:space", self.xml_space.as_ref())
+            .attr("xml:whitespace", self.xml_whitespace.as_ref())
+            .text(&self.text)
+    }
+}
+
+impl ToNode for DocDeprecated {
+    fn to_node(&self) -> Node {
+        Node::new("doc-deprecated")
+            .attr("xml:space", self.xml_space.as_ref())
+            .attr("xml:whitespace", self.xml_whitespace.as_ref())
+            .text(&self.text)
+    }
+}
+
+impl ToNode for SourcePosition {
+    fn to_node(&self) -> Node {
+        Node::new("source-position")
+            .attr("filename", Some(&self.filename))
+            .attr("line", Some(&self.line))
+            .attr("column", self.column.as_ref())
+    }
+}
+
+impl ToNode for DocSection {
+    fn to_node(&self) -> Node {
+        Node::new("docsection")
+            .attr("name", Some(&self.name))
+            .children(&self.elements)
+    }
+}
+
+impl ToNode for Alias {
+    fn to_node(&self) -> Node {
+        Node::new("alias")
+            .attr("name", Some(&self.name))
+            .attr("c:type", Some(&self.c_type))
+            .info(&self.info)
+            .children(&self.info_elements)
+            .child(self.r#type.as_ref())
+    }
+}
+
+impl ToNode for Constant {
+    fn to_node(&self) -> Node {
+        Node::new("constant")
+            .attr("name", Some(&self.name))
+            .attr("value", Some(&self.value))
+            .attr("c:type", self.c_type.as_ref())
+            .attr("c:identifier", self.c_identifier.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .child(self.r#type.as_ref())
+    }
+}
+
+impl ToNode for Type {
+    fn to_node(&self) -> Node {
+        Node::new("type")
+            .attr("name", self.name.as_ref())
+            .attr("c:type", self.c_type.as_ref())
+            .attr("introspectable", self.introspectable.as_ref())
+            .children(&self.doc_elements)
+            .children(&self.elements)
+    }
+}
+
+impl ToNode for Array {
+    fn to_node(&self) -> Node {
+        Node::new("array")
+            .attr("name", self.name.as_ref())
+            .attr("zero-terminated", self.zero_terminated.as_ref())
+            .attr("fixed-size", self.fixed_size.as_ref())
+            .attr("introspectable", self.introspectable.as_ref())
+            .attr("length", self.length.as_ref())
+            .attr("c:type", self.c_type.as_ref())
+            .children(&self.elements)
+    }
+}
+
+impl ToNode for Member {
+    fn to_node(&self) -> Node {
+        Node::new("member")
+            .attr("name", Some(&self.name))
+            .attr("value", Some(&self.value))
+            .attr("c:identifier", Some(&self.c_identifier))
+            .attr("glib:nick", self.glib_nick.as_ref())
+            .attr("glib:name", self.glib_name.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+    }
+}
+
+impl ToNode for Enumeration {
+    fn to_node(&self) -> Node {
+        Node::new("enumeration")
+            .attr("name", Some(&self.name))
+            .attr("c:type", Some(&self.c_type))
+            .attr("glib:type-name", self.glib_type_name.as_ref())
+            .attr("glib:get-type", self.glib_get_type.as_ref())
+            .attr("glib:error-domain", self.glib_error_domain.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .children(&self.members)
+            .children(&self.functions)
+            .children(&self.inline_functions)
+    }
+}
+
+impl ToNode for Bitfield {
+    fn to_node(&self) -> Node {
+        Node::new("bitfield")
+            .attr("name", Some(&self.name))
+            .attr("c:type", Some(&self.c_type))
+            .attr("glib:type-name", self.glib_type_name.as_ref())
+            .attr("glib:get-type", self.glib_get_type.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .children(&self.members)
+            .children(&self.functions)
+            .children(&self.inline_functions)
+    }
+}
+
+impl ToNode for Boxed {
+    fn to_node(&self) -> Node {
+        Node::new("glib:boxed")
+            .attr("glib:name", Some(&self.glib_name))
+            .attr("c:symbol-prefix", self.c_symbol_prefix.as_ref())
+            .attr("glib:type-name", self.glib_type_name.as_ref())
+            .attr("glib:get-type", self.glib_get_type.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .children(&self.functions)
+            .children(&self.inline_functions)
+    }
+}
+
+impl ToNode for Callback {
+    fn to_node(&self) -> Node {
+        Node::new("callback")
+            .attr("name", Some(&self.name))
+            .attr("c:type", self.c_type.as_ref())
+            .attr("throws", self.throws.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .child(self.return_value.as_ref())
+            .child(self.parameter.as_ref())
+    }
+}
+
+impl ToNode for Parameters {
+    fn to_node(&self) -> Node {
+        Node::new("parameters")
+            .child(self.instance_parameter.as_ref())
+            .children(&self.parameters)
+    }
+}
+
+impl ToNode for InstanceParameter {
+    fn to_node(&self) -> Node {
+        Node::new("instance-parameter")
+            .attr("name", Some(&self.name))
+            .attr("nullable", self.nullable.as_ref())
+            .attr("allow-none", self.allow_none.as_ref())
+            .attr("direction", self.direction.as_ref())
+            .attr("caller-allocates", self.caller_allocates.as_ref())
+            .attr("transfer-ownership", self.transfer_ownership.as_ref())
+            .children(&self.doc_elements)
+            .child(self.r#type.as_ref())
+    }
+}
+
+impl ToNode for VarArgs {
+    fn to_node(&self) -> Node {
+        Node::new("varargs")
+    }
+}
+
+impl ToNode for Parameter {
+    fn to_node(&self) -> Node {
+        Node::new("parameter")
+            .attr("name", self.name.as_ref())
+            .attr("nullable", self.nullable.as_ref())
+            .attr("allow-none", self.allow_none.as_ref())
+            .attr("introspectable", self.introspectable.as_ref())
+            .attr("closure", self.closure.as_ref())
+            .attr("destroy", self.destroy.as_ref())
+            .attr("scope", self.scope.as_ref())
+            .attr("direction", self.direction.as_ref())
+            .attr("caller-allocates", self.caller_allocates.as_ref())
+            .attr("optional", self.optional.as_ref())
+            .attr("skip", self.skip.as_ref())
+            .attr("transfer-ownership", self.transfer_ownership.as_ref())
+            .children(&self.doc_elements)
+            .children(&self.annotations)
+            .child(self.varargs.as_ref())
+            .child(self.r#type.as_ref())
+    }
+}
+
+impl ToNode for ReturnValue {
+    fn to_node(&self) -> Node {
+        Node::new("return-value")
+            .attr("introspectable", self.introspectable.as_ref())
+            .attr("nullable", self.nullable.as_ref())
+            .attr("closure", self.closure.as_ref())
+            .attr("scope", self.scope.as_ref())
+            .attr("destroy", self.destroy.as_ref())
+            .attr("skip", self.skip.as_ref())
+            .attr("allow-none", self.allow_none.as_ref())
+            .attr("transfer-ownership", self.transfer_ownership.as_ref())
+            .children(&self.doc_elements)
+            .children(&self.annotations)
+            .child(self.r#type.as_ref())
+    }
+}
+
+impl ToNode for Function {
+    fn to_node(&self) -> Node {
+        Node::new("function")
+            .callable(&self.attrs)
+            .children(&self.info_elements)
+            .child(self.return_value.as_ref())
+            .child(self.parameters.as_ref())
+    }
+}
+
+impl ToNode for FunctionInline {
+    fn to_node(&self) -> Node {
+        Node::new("function-inline")
+            .callable(&self.attrs)
+            .children(&self.doc_elements)
+            .child(self.return_value.as_ref())
+            .child(self.parameters.as_ref())
+    }
+}
+
+impl ToNode for FunctionMacro {
+    fn to_node(&self) -> Node {
+        Node::new("function-macro")
+            .callable(&self.attrs)
+            .children(&self.info_elements)
+            .child(self.parameters.as_ref())
+    }
+}
+
+impl ToNode for Constructor {
+    fn to_node(&self) -> Node {
+        Node::new("constructor")
+            .callable(&self.attrs)
+            .children(&self.info_elements)
+            .child(self.return_value.as_ref())
+            .child(self.parameters.as_ref())
+    }
+}
+
+impl ToNode for Method {
+    fn to_node(&self) -> Node {
+        Node::new("method")
+            .callable(&self.attrs)
+            .attr("glib:set-property", self.glib_set_property.as_ref())
+            .attr("glib:get-property", self.glib_get_property.as_ref())
+            .children(&self.info_elements)
+            .child(self.return_value.as_ref())
+            .child(self.parameters.as_ref())
+    }
+}
+
+impl ToNode for MethodInline {
+    fn to_node(&self) -> Node {
+        Node::new("method-inline")
+            .callable(&self.attrs)
+            .children(&self.info_elements)
+            .child(self.return_value.as_ref())
+            .child(self.parameters.as_ref())
+    }
+}
+
+impl ToNode for VirtualMethod {
+    fn to_node(&self) -> Node {
+        Node::new("virtual-method")
+            .callable(&self.attrs)
+            .attr("invoker", self.invoker.as_ref())
+            .children(&self.info_elements)
+            .child(self.return_value.as_ref())
+            .child(self.parameters.as_ref())
+    }
+}
+
+impl ToNode for Field {
+    fn to_node(&self) -> Node {
+        Node::new("field")
+            .attr("name", Some(&self.name))
+            .attr("writable", self.writable.as_ref())
+            .attr("readable", self.readable.as_ref())
+            .attr("private", self.private.as_ref())
+            .attr("bits", self.bits.as_ref())
+            .attr("transfer-ownership", self.transfer_ownership.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .child(self.callback.as_ref())
+            .child(self.r#type.as_ref())
+    }
+}
+
+impl ToNode for Property {
+    fn to_node(&self) -> Node {
+        Node::new("property")
+            .attr("name", Some(&self.name))
+            .attr("writable", self.writable.as_ref())
+            .attr("readable", self.readable.as_ref())
+            .attr("construct", self.construct.as_ref())
+            .attr("construct-only", self.construct_only.as_ref())
+            .attr("setter", self.setter.as_ref())
+            .attr("getter", self.getter.as_ref())
+            .attr("default-value", self.default_value.as_ref())
+            .attr("transfer-ownership", self.transfer_ownership.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .child(self.r#type.as_ref())
+    }
+}
+
+impl ToNode for Signal {
+    fn to_node(&self) -> Node {
+        Node::new("glib:signal")
+            .attr("name", Some(&self.name))
+            .attr("detailed", self.detailed.as_ref())
+            .attr("when", self.when.as_ref())
+            .attr("action", self.action.as_ref())
+            .attr("no-hooks", self.no_hooks.as_ref())
+            .attr("no-recurse", self.no_recurse.as_ref())
+            .attr("emitter", self.emitter.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .child(self.return_value.as_ref())
+            .child(self.parameters.as_ref())
+    }
+}
+
+impl ToNode for Implements {
+    fn to_node(&self) -> Node {
+        Node::new("implements").attr("name", Some(&self.name))
+    }
+}
+
+impl ToNode for Prerequisite {
+    fn to_node(&self) -> Node {
+        Node::new("prerequisite").attr("name", Some(&self.name))
+    }
+}
+
+impl ToNode for Class {
+    fn to_node(&self) -> Node {
+        Node::new("class")
+            .attr("name", Some(&self.name))
+            .attr("c:symbol-prefix", self.c_symbol_prefix.as_ref())
+            .attr("c:type", self.c_type.as_ref())
+            .attr("parent", self.parent.as_ref())
+            .attr("glib:type-name", Some(&self.glib_type_name))
+            .attr("glib:get-type", Some(&self.glib_get_type))
+            .attr("glib:type-struct", self.glib_type_struct.as_ref())
+            .attr("glib:ref-func", self.glib_ref_func.as_ref())
+            .attr("glib:unref-func", self.glib_unref_func.as_ref())
+            .attr("glib:set-value-func", self.glib_set_value_func.as_ref())
+            .attr("glib:get-value-func", self.glib_get_value_func.as_ref())
+            .attr("abstract", self.r#abstract.as_ref())
+            .attr("final", self.r#final.as_ref())
+            .attr("glib:fundamental", self.glib_fundamental.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .children(&self.implements)
+            .children(&self.constructors)
+            .children(&self.functions)
+            .children(&self.inline_functions)
+            .children(&self.virtual_methods)
+            .children(&self.methods)
+            .children(&self.inline_methods)
+            .children(&self.properties)
+            .children(&self.fields)
+            .children(&self.signals)
+            .children(&self.unions)
+            .children(&self.constants)
+            .children(&self.records)
+            .children(&self.callbacks)
+    }
+}
+
+impl ToNode for Interface {
+    fn to_node(&self) -> Node {
+        Node::new("interface")
+            .attr("name", Some(&self.name))
+            .attr("c:symbol-prefix", self.c_symbol_prefix.as_ref())
+            .attr("c:type", self.c_type.as_ref())
+            .attr("glib:type-name", Some(&self.glib_type_name))
+            .attr("glib:get-type", Some(&self.glib_get_type))
+            .attr("glib:type-struct", self.glib_type_struct.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .children(&self.prerequisites)
+            .children(&self.implements)
+            .children(&self.constructors)
+            .children(&self.functions)
+            .children(&self.inline_functions)
+            .children(&self.virtual_methods)
+            .children(&self.methods)
+            .children(&self.inline_methods)
+            .children(&self.properties)
+            .children(&self.fields)
+            .children(&self.signals)
+            .children(&self.callbacks)
+            .children(&self.constants)
+    }
+}
+
+impl ToNode for Record {
+    fn to_node(&self) -> Node {
+        Node::new("record")
+            .attr("name", Some(&self.name))
+            .attr("c:type", self.c_type.as_ref())
+            .attr("disguised", self.disguised.as_ref())
+            .attr("opaque", self.opaque.as_ref())
+            .attr("pointer", self.pointer.as_ref())
+            .attr("glib:type-name", self.glib_type_name.as_ref())
+            .attr("glib:get-type", self.glib_get_type.as_ref())
+            .attr("c:symbol-prefix", self.c_symbol_prefix.as_ref())
+            .attr("foreign", self.foreign.as_ref())
+            .attr(
+                "glib:is-gtype-struct-for",
+                self.glib_is_gtype_struct_for.as_ref(),
+            )
+            .attr("copy-function", self.copy_function.as_ref())
+            .attr("free-function", self.free_function.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .children(&self.fields)
+            .children(&self.constructors)
+            .children(&self.functions)
+            .children(&self.inline_functions)
+            .children(&self.methods)
+            .children(&self.inline_methods)
+            .children(&self.unions)
+    }
+}
+
+impl ToNode for Union {
+    fn to_node(&self) -> Node {
+        Node::new("union")
+            .attr("name", self.name.as_ref())
+            .attr("c:type", self.c_type.as_ref())
+            .attr("c:symbol-prefix", self.c_symbol_prefix.as_ref())
+            .attr("glib:type-name", self.glib_type_name.as_ref())
+            .attr("glib:get-type", self.glib_get_type.as_ref())
+            .attr("copy-function", self.copy_function.as_ref())
+            .attr("free-function", self.free_function.as_ref())
+            .info(&self.info)
+            .children(&self.info_elements)
+            .children(&self.fields)
+            .children(&self.constructors)
+            .children(&self.functions)
+            .children(&self.inline_functions)
+            .children(&self.methods)
+            .children(&self.inline_methods)
+            .children(&self.records)
+    }
+}
+
+impl Repository {
+    /// Serialize the repository into a normalized GIR document.
+    pub fn to_gir_xml(&self) -> String {
+        let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+
+        // writing into a Vec cannot fail
+        writer
+            .write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))
+            .and_then(|_| self.to_node().write(&mut writer))
+            .expect("in memory write");
+
+        let mut xml = String::from_utf8(writer.into_inner()).expect("valid utf8");
+        xml.push('\n');
+        xml
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse_gir::{ParseOptions, parse_with_options};
+
+    const GIR: &str = r#"<?xml version="1.0"?>
+<repository version="1.2" xmlns="http://www.gtk.org/introspection/core/1.0" xmlns:c="http://www.gtk.org/introspection/c/1.0" xmlns:glib="http://www.gtk.org/introspection/glib/1.0">
+  <include name="GObject" version="2.0"/>
+  <package name="demo-1.0"/>
+  <c:include name="demo.h"/>
+  <namespace name="Demo" version="1.0" shared-library="libdemo.so" c:identifier-prefixes="Demo" c:symbol-prefixes="demo">
+    <alias name="Id" c:type="DemoId">
+      <type name="guint64" c:type="guint64"/>
+    </alias>
+    <constant name="MAX" value="10" c:type="DEMO_MAX">
+      <type name="gint" c:type="gint"/>
+    </constant>
+    <callback name="Func" c:type="DemoFunc">
+      <return-value transfer-ownership="none">
+        <type name="none" c:type="void"/>
+      </return-value>
+      <parameters>
+        <parameter name="user_data" transfer-ownership="none" nullable="1" closure="0">
+          <type name="gpointer" c:type="gpointer"/>
+        </parameter>
+      </parameters>
+    </callback>
+    <class name="Widget" c:type="DemoWidget" parent="GObject.Object" glib:type-name="DemoWidget" glib:get-type="demo_widget_get_type" glib:type-struct="WidgetClass">
+      <doc xml:space="preserve"><![CDATA[A <widget> & friends]]></doc>
+      <constructor name="new" c:identifier="demo_widget_new">
+        <return-value transfer-ownership="full">
+          <type name="Widget" c:type="DemoWidget*"/>
+        </return-value>
+      </constructor>
+      <method name="get_name" c:identifier="demo_widget_get_name">
+        <return-value transfer-ownership="none" nullable="1">
+          <type name="utf8" c:type="const char*"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="self" transfer-ownership="none">
+            <type name="Widget" c:type="DemoWidget*"/>
+          </instance-parameter>
+        </parameters>
+      </method>
+      <method name="foreach" c:identifier="demo_widget_foreach" throws="1">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <instance-parameter name="self" transfer-ownership="none">
+            <type name="Widget" c:type="DemoWidget*"/>
+          </instance-parameter>
+          <parameter name="func" transfer-ownership="none" scope="notified" closure="1" destroy="2">
+            <type name="Func" c:type="DemoFunc"/>
+          </parameter>
+          <parameter name="user_data" transfer-ownership="none" nullable="1" allow-none="1">
+            <type name="gpointer" c:type="gpointer"/>
+          </parameter>
+          <parameter name="destroy" transfer-ownership="none" scope="async">
+            <type name="GLib.DestroyNotify" c:type="GDestroyNotify"/>
+          </parameter>
+          <parameter name="names" direction="out" caller-allocates="0" transfer-ownership="full">
+            <array c:type="char***">
+              <type name="utf8"/>
+            </array>
+          </parameter>
+        </parameters>
+      </method>
+      <property name="label" writable="1" construct="1" transfer-ownership="none" getter="get_name">
+        <type name="utf8" c:type="gchar*"/>
+      </property>
+      <glib:signal name="changed" when="last" detailed="1" action="1">
+        <return-value transfer-ownership="none">
+          <type name="none" c:type="void"/>
+        </return-value>
+        <parameters>
+          <parameter name="value" transfer-ownership="none">
+            <type name="gint" c:type="gint"/>
+          </parameter>
+        </parameters>
+      </glib:signal>
+    </class>
+    <record name="WidgetClass" c:type="DemoWidgetClass" glib:is-gtype-struct-for="Widget">
+      <field name="parent_class" readable="0" private="1">
+        <type name="GObject.ObjectClass" c:type="GObjectClass"/>
+      </field>
+    </record>
+    <enumeration name="Mode" c:type="DemoMode" glib:type-name="DemoMode" glib:get-type="demo_mode_get_type">
+      <member name="off" value="0" c:identifier="DEMO_MODE_OFF" glib:nick="off"/>
+      <member name="on" value="1" c:identifier="DEMO_MODE_ON" glib:nick="on"/>
+    </enumeration>
+    <bitfield name="Flags" c:type="DemoFlags">
+      <member name="a" value="1" c:identifier="DEMO_FLAGS_A"/>
+      <member name="b" value="2" c:identifier="DEMO_FLAGS_B"/>
+    </bitfield>
+    <function name="init" c:identifier="demo_init" deprecated="1" deprecated-version="1.2" version="1.0">
+      <return-value transfer-ownership="none">
+        <type name="gboolean" c:type="gboolean"/>
+      </return-value>
+    </function>
+  </namespace>
+</repository>
+"#;
+
+    fn parse_strict(gir: &str) -> crate::element::Repository {
+        let options = ParseOptions { strict: true };
+        let (repo, events) = parse_with_options(gir, &options).expect("valid GIR");
+        assert!(events.is_empty(), "skipped: {events:?}");
+        repo
+    }
+
+    #[test]
+    fn round_trip() {
+        let repo = parse_strict(GIR);
+        let xml = repo.to_gir_xml();
+        let reparsed = parse_strict(&xml);
+
+        let json = serde_json::to_value(&reparsed).unwrap();
+        assert_eq!(serde_json::to_value(&repo).unwrap(), json);
+        assert!(json.to_string().contains("A <widget> & friends"));
+        assert_eq!(xml, reparsed.to_gir_xml());
+    }
+}
@@ -0,0 +1,65 @@
+use regex::Regex;
+use std::{fmt, str::FromStr};
+
+/// Namespace filter matched against a file stem like "Gtk-4.0".
+/// Plain values are globs where `*` and `?` are wildcards, values
+/// wrapped in slashes like `/^Gtk-[34]/` are regular expressions.
+#[derive(Debug, Clone, serde::Deserialize)]
+#[serde(try_from = "String")]
+pub struct Pattern {
+    source: String,
+    regex: Regex,
+}
+
+impl Pattern {
+    pub fn matches(&self, name: &str) -> bool {
+        self.regex.is_match(name)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+}
+
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+impl FromStr for Pattern {
+    type Err = regex::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let regex = match s.strip_prefix('/').and_then(|s| s.strip_suffix('/')) {
+            Some(regex) => Regex::new(regex)?,
+            None => Regex::new(&glob_to_regex(s))?,
+        };
+
+        Ok(Self {
+            source: s.to_owned(),
+            regex,
+        })
+    }
+}
+
+impl TryFrom<String> for Pattern {
+    type Error = regex::Error;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl fmt::Display for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
@@ -0,0 +1,58 @@
+use crate::generator::Event;
+use std::collections::BTreeMap;
+
+/// Symbols of a namespace that made it into the output, and the ones that failed
+#[derive(Debug, Default, Clone, Copy, serde::Serialize)]
+pub struct Rendered {
+    pub symbols: usize,
+    pub skipped: usize,
+}
+
+/// Summary of a run, collected from the emitted events.
+#[derive(Debug, Default, Clone, serde::Serialize)]
+pub struct Report {
+    pub parsed: usize,
+    pub parse_failed: usize,
+    pub ignored: usize,
+    pub generated: usize,
+    pub cache_hits: usize,
+    /// Stale files deleted from the output directory
+    pub removed: usize,
+    pub warnings: usize,
+    /// Keyed by name and version, e.g "Gtk-4.0"
+    pub namespaces: BTreeMap<String, Rendered>,
+    /// Messages of every parse and render failure
+    pub failures: Vec<String>,
+}
+
+impl Report {
+    pub fn record(&mut self, event: &Event) {
+        match event {
+            Event::Parsed { .. } => self.parsed += 1,
+            Event::ParseFailed { file_path, err, .. } => {
+                self.parse_failed += 1;
+                self.failures
+                    .push(format!("could not parse {}: {}", file_path.display(), err));
+            }
+            Event::Ignored { .. } => self.ignored += 1,
+            Event::Warning { .. } => self.warnings += 1,
+            Event::Problem { .. } => {}
+            Event::Failed { repo, err, .. } => self.failures.push(match repo {
+                Some(repo) => format!("failed to render {repo}: {err}"),
+                None => err.to_string(),
+            }),
+            Event::Generated { .. } => self.generated += 1,
+            Event::CacheHit { .. } => self.cache_hits += 1,
+            Event::Removed { .. } => self.removed += 1,
+            Event::Rendered {
+                namespace,
+                symbols,
+                skipped,
+            } => {
+                let rendered = self.namespaces.entry(namespace.to_string()).or_default();
+                rendered.symbols += symbols;
+                rendered.skipped += skipped;
+            }
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use super::{
+    Args, STDIN, gir_paths, include_closure, include_names, keep, name_parsed, parse_file,
+    read_stdin, report,
+};
+use crate::element::Repository;
+use crate::generator::{Error, Event, EventSink, Generator, Gir};
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+use std::{fs, thread};
+
+struct Loaded {
+    name: String,
+    modified: Option<SystemTime>,
+    /// `None` when parsing failed, it is only retried once the file changes
+    parsed: Option<(Repository, String)>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|meta| meta.modified()).ok()
+}
+
+fn stem(path: &Path) -> String {
+    path.file_stem()
+        .map(|stem| stem.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+struct Watcher<'w, 'a, T: Generator + Sync> {
+    args: &'w Args<'a, T>,
+    /// Every parsed file, stdin being kept under [`STDIN`]
+    loaded: HashMap<PathBuf, Loaded>,
+    /// Files used by the last run
+    selected: Vec<PathBuf>,
+    /// Modification times of [`Generator::extra_inputs`]
+    extra: HashMap<PathBuf, Option<SystemTime>>,
+}
+
+impl<T: Generator + Sync> Watcher<'_, '_, T> {
+    /// Parses the files that are new or were modified since they were last parsed,
+    /// `explicit` ones being named after their namespace. Returns whether there were any.
+    fn refresh(&mut self, paths: &[&Path], explicit: bool) -> bool {
+        let stale: Vec<(&Path, Option<SystemTime>)> = paths
+            .iter()
+            .map(|path| (*path, modified(path)))
+            .filter(|(path, modified)| {
+                self.loaded
+                    .get(*path)
+                    .is_none_or(|loaded| loaded.modified != *modified)
+            })
+            .collect();
+
+        let parsed: Vec<(PathBuf, Loaded)> = stale
+            .par_iter()
+            .map(|(path, modified)| {
+                let parsed = report(path, parse_file(path, &self.args.parse_options), self.args);
+                let (name, parsed) = match name_parsed(path, parsed, explicit, self.args) {
+                    Some((name, repo, contents)) => (name, Some((repo, contents))),
+                    None => (stem(path), None),
+                };
+                let loaded = Loaded {
+                    name,
+                    modified: *modified,
+                    parsed,
+                };
+                (path.to_path_buf(), loaded)
+            })
+            .collect();
+
+        let changed = !parsed.is_empty();
+        self.loaded.extend(parsed);
+        changed
+    }
+
+    fn includes(&self, path: &Path) -> Vec<String> {
+        self.loaded
+            .get(path)
+            .and_then(|loaded| loaded.parsed.as_ref())
+            .map(|(repo, _)| include_names(repo))
+            .unwrap_or_default()
+    }
+
+    /// Selects and refreshes the files to use, with `only` set these are the requested
+    /// namespaces and everything they include. Files that are not used are reported to
+    /// `event`. Returns whether anything changed.
+    fn update(&mut self, event: EventSink) -> Result<bool, Error> {
+        let args = self.args;
+        let stdin = Path::new(STDIN);
+        let files: Vec<&Path> = args
+            .files
+            .iter()
+            .map(PathBuf::as_path)
+            .filter(|path| *path != stdin)
+            .collect();
+        let mut changed = self.refresh(&files, true);
+
+        let mut seen = HashSet::new();
+        let explicit: Vec<(&Path, String)> = args
+            .files
+            .iter()
+            .filter_map(|path| {
+                let loaded = self.loaded.get(path)?;
+                loaded.parsed.as_ref()?;
+                Some((path.as_path(), loaded.name.clone()))
+            })
+            .filter(|(path, name)| keep(args, &mut seen, path, name, event))
+            .collect();
+
+        let paths = gir_paths(args, seen, event);
+
+        let selected: Vec<PathBuf> = if args.only.is_empty() {
+            changed |= self.refresh(
+                &paths.iter().map(PathBuf::as_path).collect::<Vec<_>>(),
+                false,
+            );
+            explicit
+                .iter()
+                .map(|(path, _)| *path)
+                .chain(paths.iter().map(PathBuf::as_path))
+                .map(Path::to_path_buf)
+                .collect()
+        } else {
+            let mut by_name: HashMap<String, &Path> = paths
+                .iter()
+                .map(|path| (stem(path), path.as_path()))
+                .collect();
+            by_name.extend(explicit.iter().map(|(path, name)| (name.clone(), *path)));
+
+            include_closure(args, &by_name, |level| {
+                let files: Vec<&Path> = level
+                    .iter()
+                    .filter(|path| !explicit.iter().any(|(explicit, _)| explicit == *path))
+                    .copied()
+                    .collect();
+                changed |= self.refresh(&files, false);
+                level.iter().map(|path| self.includes(path)).collect()
+            })?
+            .into_iter()
+            .map(Path::to_path_buf)
+            .collect()
+        };
+
+        // files that are gone are parsed again should they come back
+        self.loaded
+            .retain(|path, _| self.args.files.contains(path) || paths.contains(path));
+
+        let extra: HashMap<PathBuf, Option<SystemTime>> = args
+            .generator
+            .extra_inputs()
+            .into_iter()
+            .map(|path| {
+                let modified = modified(&path);
+                (path, modified)
+            })
+            .collect();
+
+        changed |= self.selected != selected || self.extra != extra;
+        self.selected = selected;
+        self.extra = extra;
+        Ok(changed)
+    }
+
+    fn generate(&mut self) -> Result<(), Error> {
+        let mut used = Vec::new();
+        for path in &self.selected {
+            if let Some(loaded) = self.loaded.remove(path) {
+                used.push((path.clone(), loaded));
+            }
+        }
+
+        let mut girs: Vec<Gir> = used
+            .iter_mut()
+            .filter_map(|(_, loaded)| {
+                let (repo, contents) = loaded.parsed.take()?;
+                Some(Gir {
+                    name: &loaded.name,
+                    repo,
+                    contents,
+                })
+            })
+            .collect();
+        girs.sort_by_key(|gir| gir.name);
+
+        let result = self.args.generator.generate(&girs, self.args.on_event);
+
+        // hand the repositories back for the next run
+        let mut parsed: HashMap<String, (Repository, String)> = girs
+            .into_iter()
+            .map(|gir| (gir.name.to_owned(), (gir.repo, gir.contents)))
+            .collect();
+
+        for (path, mut loaded) in used {
+            loaded.parsed = parsed.remove(&loaded.name);
+            self.loaded.insert(path, loaded);
+        }
+
+        result
+    }
+}
+
+fn warn(err: Error, args: &Args<'_, impl Generator + Sync>) {
+    let warning = match err {
+        Error::Empty => "nothing to generate".to_owned(),
+        Error::FsError(err) => err.to_string(),
+        Error::Invalid(n) => format!("found {n} problems"),
+        Error::Missing {
+            namespace,
+            included_by: Some(repo),
+        } => format!("{repo} includes {namespace}, which was not found"),
+        Error::Missing { namespace, .. } => format!("{namespace} was not found"),
+        Error::Filtered {
+            namespace,
+            included_by: Some(repo),
+            cause,
+        } => format!("{repo} includes {namespace}, which {cause}"),
+        Error::Filtered {
+            namespace, cause, ..
+        } => format!("{namespace} {cause}"),
+    };
+    (args.on_event)(Event::Warning { warning: &warning });
+}
+
+/// Runs the generator, then polls the dirs, files and [`Generator::extra_inputs`] every
+/// `interval` and runs it again whenever one is added, removed or modified. Only changed
+/// files are parsed again, the other repositories are kept in memory. The generator is
+/// given every repository, so only a caching one like [`crate::generator::gjs`] avoids
+/// rendering unchanged namespaces again. Returns only when the first run fails.
+pub fn watch<T: Generator + Sync>(args: Args<'_, T>, interval: Duration) -> Result<(), Error> {
+    let mut watcher = Watcher {
+        args: &args,
+        loaded: HashMap::new(),
+        selected: Vec::new(),
+        extra: HashMap::new(),
+    };
+
+    // stdin can only be read once, so it is kept for every run
+    if args.files.iter().any(|path| path == Path::new(STDIN)) {
+        let path = Path::new(STDIN);
+        let parsed = name_parsed(path, read_stdin(&args), true, &args);
+        let loaded = Loaded {
+            name: parsed
+                .as_ref()
+                .map(|(name, ..)| name.clone())
+                .unwrap_or_default(),
+            modified: None,
+            parsed: parsed.map(|(_, repo, contents)| (repo, contents)),
+        };
+        watcher.loaded.insert(path.to_path_buf(), loaded);
+    }
+
+    // files that are not used are only reported once
+    watcher.update(args.on_event)?;
+    watcher.generate()?;
+
+    loop {
+        thread::sleep(interval);
+
+        match watcher.update(&|_| {}) {
+            Ok(false) => {}
+            Ok(true) => {
+                if let Err(err) = watcher.generate() {
+                    warn(err, &args);
+                }
+            }
+            Err(err) => warn(err, &args),
+        }
+    }
+}